@@ -1,7 +1,25 @@
 pub use clap::Parser;
+use clap::ValueEnum;
+use pipec_errors::ErrorFormat;
 use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct Args {
-    pub file: PathBuf,
+    #[arg(required_unless_present = "explain")]
+    pub file: Option<PathBuf>,
+    /// How diagnostics are printed, either `human` or `json`.
+    #[arg(long, default_value = "human")]
+    pub error_format: ErrorFormat,
+    /// What to build, either `bin` or `lib`. Only a `bin` needs a `main` function.
+    #[arg(long, value_enum, default_value_t = CrateType::Lib)]
+    pub crate_type: CrateType,
+    /// Prints the explanation of an error code, eg. `E0001`, instead of compiling.
+    #[arg(long, value_name = "CODE")]
+    pub explain: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum CrateType {
+    Bin,
+    Lib,
 }
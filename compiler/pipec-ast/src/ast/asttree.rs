@@ -1,14 +1,16 @@
-use super::ASTNode;
+use super::{ASTNode, Docs, Spanned};
 use pipec_file_loader::FileId;
 
 #[derive(Debug, Clone)]
 pub struct ASTTree {
-    pub stream: Vec<ASTNode>,
+    pub stream: Vec<Spanned<ASTNode>>,
     pub id: FileId,
+    /// The `//!` comments at the start of the file or block.
+    pub docs: Docs,
 }
 
 impl ASTTree {
-    pub fn new(stream: Vec<ASTNode>, id: FileId) -> Self {
-        Self { stream, id }
+    pub fn new(stream: Vec<Spanned<ASTNode>>, id: FileId, docs: Docs) -> Self {
+        Self { stream, id, docs }
     }
 }
@@ -1,7 +1,9 @@
 #![allow(unused_must_use)]
+use pipec_errors::{Diagnostic, DiagnosticEmitter, ErrorKind, Label, Suggestion};
 use pipec_file_loader::{FileId, FileLoader};
 use pipec_span::Span;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::ops::Deref;
 use std::path::PathBuf;
 
 use crate::RecursiveGuard;
@@ -12,6 +14,12 @@ use crate::tokenizer::Tokenizer;
 use crate::tokenizer::tokentree::TokenTree;
 pub mod asttree;
 
+/// Returned by the parser after the diagnostic describing the failure has been emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
 pub struct ASTGenerator<'this> {
     src: FileId,
     tokens: &'this mut TokenTree<'this>,
@@ -19,19 +27,25 @@ pub struct ASTGenerator<'this> {
     pub loader: &'this mut FileLoader,
     arena: &'this mut pipec_arena::Arena,
     path: PathBuf,
+    diagnostics: DiagnosticEmitter,
 }
 
 impl<'this> ASTGenerator<'this> {
     pub fn tree(mut self) -> ASTTree {
+        let docs = self.consume_inner_docs();
         let mut out = Vec::new();
         loop {
-            let next = self.parse_value();
-            if matches!(next, ASTNode::EOF) {
+            let next = self.parse_value_recovering();
+            if matches!(next.node, ASTNode::EOF) {
                 break;
             }
+            // a `}` at the top level has no block to close, parsing it was already reported
+            if matches!(next.node, ASTNode::Error) && self.next_is(Token::RightCurly) {
+                self.advance_stream();
+            }
             out.push(next);
         }
-        ASTTree::new(out, self.src)
+        ASTTree::new(out, self.src, docs)
     }
 
     pub fn file_id(&self) -> FileId {
@@ -45,6 +59,7 @@ impl<'this> ASTGenerator<'this> {
         arena: &'this mut pipec_arena::Arena,
         guard: &'this mut RecursiveGuard,
         loader: &'this mut FileLoader,
+        diagnostics: DiagnosticEmitter,
     ) -> Self {
         let path = path.parent().unwrap().to_path_buf();
         Self {
@@ -54,6 +69,7 @@ impl<'this> ASTGenerator<'this> {
             guard,
             arena,
             loader,
+            diagnostics,
         }
     }
 
@@ -62,123 +78,264 @@ impl<'this> ASTGenerator<'this> {
         self.tokens.next_token()
     }
     #[inline]
-    pub(crate) fn peek_stream(&mut self) -> &Option<Token> {
+    pub(crate) fn peek_stream(&mut self) -> Option<&Token> {
         self.tokens.peek()
     }
 
-    pub fn parse_value(&mut self) -> ASTNode {
-        match self.peek_stream() {
+    /// Returns where the next node begins, the start of the next token.
+    #[inline]
+    pub(crate) fn node_begin(&mut self) -> usize {
+        self.tokens.peek_span().begin
+    }
+
+    /// Attaches the span from `begin` to the end of the last consumed token to a node.
+    #[inline]
+    pub(crate) fn spanned<T>(&self, begin: usize, node: T) -> Spanned<T> {
+        let end = self.tokens.last_span().end.max(begin);
+        Spanned::new(node, Span { begin, end }, self.src)
+    }
+
+    /// Emits the diagnostic and returns the error the parser unwinds with.
+    #[inline]
+    pub(crate) fn report<T>(&mut self, diagnostic: Diagnostic) -> ParseResult<T> {
+        self.diagnostics.emit(diagnostic);
+        Err(ParseError)
+    }
+
+    /// Reports the next token as unexpected, `expected` describes what the parser was looking for.
+    pub(crate) fn unexpected<T>(&mut self, expected: &str) -> ParseResult<T> {
+        let diagnostic = self.unexpected_diagnostic(expected);
+        self.report(diagnostic)
+    }
+
+    pub(crate) fn unexpected_diagnostic(&mut self, expected: &str) -> Diagnostic {
+        let label = Label::new(self.src, self.tokens.peek_span());
+        match self.peek_stream().copied() {
+            Some(found) => Diagnostic::error(
+                ErrorKind::UnexpectedToken,
+                format!("expected {expected}, found {found}"),
+            )
+            .with_primary(label.with_message(format!("expected {expected}"))),
+            None => Diagnostic::error(
+                ErrorKind::UnexpectedEndOfFile,
+                format!("expected {expected}, found end of file"),
+            )
+            .with_primary(label),
+        }
+    }
+
+    pub fn parse_value(&mut self) -> ParseResult<Spanned<ASTNode>> {
+        let begin = self.node_begin();
+        let node = match self.peek_stream() {
             Some(v) => match v {
                 Token::UsingKeyword => self.consume_using_keyword(),
                 Token::ModuleKeyword => self.consume_module_keyword(),
                 Token::ComponentKeyword => self.consume_component_keyword(),
                 Token::ViewportKeyword => self.consume_viewport_keyword(),
                 Token::FunctionKeyword => self.consume_function_keyword(),
+                Token::StaticKeyword | Token::ConstKeyword => self.consume_static_keyword(),
                 Token::PublicKeyword => self.consume_public_keyword(),
                 Token::TypeKeyword => self.consume_type_keyword(),
                 Token::TraitKeyword => self.consume_trait_keyword(),
                 Token::ImplementKeyword => self.consume_implement_keyword(),
                 Token::AtSign => self.consume_attributes(),
-                _v => {
-                    todo!();
-                }
+                Token::DocComment(_) => self.consume_documented_item(),
+                _v => self.unexpected("an item"),
             },
-            None => ASTNode::EOF,
+            None => Ok(ASTNode::EOF),
+        }?;
+        Ok(self.spanned(begin, node))
+    }
+
+    /// Parses an item, on failure the tokens of the item are skipped and an ASTNode::Error takes its place.
+    #[inline]
+    pub(crate) fn parse_value_recovering(&mut self) -> Spanned<ASTNode> {
+        let depth = self.tokens.depth();
+        let begin = self.node_begin();
+        match self.parse_value() {
+            Ok(v) => v,
+            Err(ParseError) => {
+                self.synchronize(depth);
+                self.spanned(begin, ASTNode::Error)
+            }
+        }
+    }
+
+    /// Skips tokens after a parse failure until the parser is back at a point it can continue from :
+    /// after a `;` or a block closed at `depth`, before the `}` closing the enclosing block, or before an item keyword.
+    pub(crate) fn synchronize(&mut self, depth: usize) {
+        loop {
+            let current = self.tokens.depth();
+            match self.peek_stream() {
+                None => return,
+                Some(Token::Semicolon) if current <= depth => {
+                    self.advance_stream();
+                    return;
+                }
+                Some(Token::RightCurly) if current <= depth => return,
+                Some(Token::RightCurly) if current == depth + 1 => {
+                    self.advance_stream();
+                    return;
+                }
+                Some(v) if v.is_item_keyword() => return,
+                _ => {
+                    self.advance_stream();
+                }
+            }
         }
     }
 
+    /// Parses items until the closing `}` of a module, trait or implement block.
     #[inline]
-    pub(crate) fn consume_implement_keyword(&mut self) -> ASTNode {
-        self.advance_stream();
-        let generics = self.consume_generics();
-        let first = self.consume_a_path();
-        let second = {
+    pub(crate) fn consume_item_block(&mut self) -> ParseResult<ASTTree> {
+        let docs = self.consume_inner_docs();
+        let mut nodes = Vec::new();
+        loop {
             match self.peek_stream() {
-                Some(Token::ForKeyword) => {
+                Some(Token::RightCurly) => {
                     self.advance_stream();
-                    Some(self.consume_a_path())
+                    break;
                 }
-                Some(Token::LeftCurly) => None,
-                v => todo!("unexpected {v:#?}"),
+                None => return self.unexpected("`}`"),
+                _ => nodes.push(self.parse_value_recovering()),
             }
+        }
+        Ok(ASTTree::new(nodes, self.src, docs))
+    }
+
+    /// Collects the `//!` comments at the start of a file or an item block.
+    #[inline]
+    pub(crate) fn consume_inner_docs(&mut self) -> Docs {
+        let mut out = Vec::new();
+        while let Some(Token::InnerDocComment(span)) = self.peek_stream().copied() {
+            self.advance_stream();
+            out.push(span);
+        }
+        Docs(out)
+    }
+
+    #[inline]
+    pub(crate) fn consume_outer_docs(&mut self) -> Docs {
+        let mut out = Vec::new();
+        while let Some(Token::DocComment(span)) = self.peek_stream().copied() {
+            self.advance_stream();
+            out.push(span);
+        }
+        Docs(out)
+    }
+
+    /// Parses an item after its `///` comments, which are attached to the declaration it holds.
+    #[inline]
+    pub(crate) fn consume_documented_item(&mut self) -> ParseResult<ASTNode> {
+        let begin = self.node_begin();
+        let docs = self.consume_outer_docs();
+        let span = Span {
+            begin,
+            end: self.tokens.last_span().end,
         };
-        let block = {
-            self.must(Token::LeftCurly);
-            let mut nodes = Vec::new();
-            loop {
-                if self.peek_stream() == &Some(Token::RightCurly) {
+        let mut item = self.parse_value()?;
+        if !item.node.document(docs) {
+            self.unused_docs(span);
+        }
+        Ok(item.node)
+    }
+
+    pub(crate) fn unused_docs(&mut self, span: Span) {
+        self.diagnostics.emit(
+            Diagnostic::warning("unused doc comment")
+                .with_primary(
+                    Label::new(self.src, span).with_message("this doc comment documents nothing"),
+                )
+                .with_help("use `//` for comments that are not documentation"),
+        );
+    }
+
+    #[inline]
+    pub(crate) fn consume_implement_keyword(&mut self) -> ParseResult<ASTNode> {
+        self.advance_stream();
+        let generics = self.consume_generics()?;
+        let first = self.consume_a_path()?;
+        let second = {
+            match self.peek_stream() {
+                Some(Token::ForKeyword) => {
                     self.advance_stream();
-                    break;
+                    Some(self.consume_a_path()?)
                 }
-                nodes.push(self.parse_value());
+                Some(Token::LeftCurly) => None,
+                _ => return self.unexpected("`for` or `{`"),
             }
-            ASTTree::new(nodes, self.src)
         };
+        self.must(Token::LeftCurly)?;
+        let block = self.consume_item_block()?;
 
         if let Some(implementor) = second {
-            ASTNode::ImplementBlock {
+            Ok(ASTNode::ImplementBlock {
                 generics,
                 traitpath: Some(first),
                 implementor,
                 block,
-            }
+            })
         } else {
-            ASTNode::ImplementBlock {
+            Ok(ASTNode::ImplementBlock {
                 generics,
                 traitpath: second,
                 implementor: first,
                 block,
-            }
+            })
         }
     }
 
     #[inline]
-    pub(crate) fn consume_trait_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_trait_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let name = self.must_ident();
-        let generics = self.consume_generics();
+        let name = self.must_ident()?;
+        let generics = self.consume_generics()?;
         let mut supertraits = Traits::default();
         if self.next_is(Token::Colon) {
             self.advance_stream();
-            supertraits = self.consume_traits();
-        }
-        self.must(Token::LeftCurly);
-        let mut nodes = Vec::new();
-        loop {
-            if self.peek_stream() == &Some(Token::RightCurly) {
-                self.advance_stream();
-                break;
-            }
-            nodes.push(self.parse_value());
+            supertraits = self.consume_traits()?;
         }
-        let tree = ASTTree::new(nodes, self.src);
-        ASTNode::TraitDeclaration {
+        self.must(Token::LeftCurly)?;
+        let tree = self.consume_item_block()?;
+        Ok(ASTNode::TraitDeclaration {
             name,
             generics,
             supertraits,
             tree,
-        }
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_attributes(&mut self) -> ASTNode {
+    pub(crate) fn consume_attributes(&mut self) -> ParseResult<ASTNode> {
         let mut attributes = Vec::new();
         loop {
             if self.next_is(Token::AtSign) {
                 self.advance_stream();
-                let name = self
-                    .must_ident()
-                    .parse_arena(self.loader.load(self.src), self.arena);
+                let span = self.must_ident()?;
+                let name = span.parse_arena(self.loader.load(self.src), self.arena);
                 match name {
-                    "language" => attributes.push(self.consume_language_attribute()),
+                    "language" => attributes.push(self.consume_language_attribute()?),
                     "inline" => attributes.push(self.consume_inline_attribute()),
-                    _ => todo!(),
+                    _ => {
+                        return self.report(
+                            Diagnostic::error(
+                                ErrorKind::UnknownAttribute,
+                                format!("unknown attribute `{name}`"),
+                            )
+                            .with_primary(Label::new(self.src, span))
+                            .with_note("the known attributes are `@language` and `@inline`"),
+                        );
+                    }
                 }
             } else {
                 break;
             }
         }
-        ASTNode::Attributed(attributes, Box::new(self.parse_value()))
+        Ok(ASTNode::Attributed(
+            attributes,
+            Box::new(self.parse_value()?),
+        ))
     }
 
     #[inline]
@@ -187,54 +344,56 @@ impl<'this> ASTGenerator<'this> {
     }
 
     #[inline]
-    pub(crate) fn consume_language_attribute(&mut self) -> Attribute {
-        self.must(Token::LeftParenthesis);
-        let name = self.must_string();
-        self.must(Token::RightParenthesis);
-        Attribute::LanguageAttribute(name)
+    pub(crate) fn consume_language_attribute(&mut self) -> ParseResult<Attribute> {
+        self.must(Token::LeftParenthesis)?;
+        let (name, raw) = self.must_string()?;
+        self.must(Token::RightParenthesis)?;
+        Ok(Attribute::LanguageAttribute { name, raw })
     }
 
     #[inline]
-    pub(crate) fn consume_type_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_type_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let name = self.must_ident();
-        let generics = self.consume_generics();
+        let name = self.must_ident()?;
+        let generics = self.consume_generics()?;
         match self.peek_stream() {
             Some(Token::EqualSign) => {
                 self.advance_stream();
-                let subtype = self.consume_subtype();
-                self.consume_a_semicolon();
-                ASTNode::TypeDeclaration {
+                let subtype = self.consume_subtype()?;
+                self.consume_a_semicolon()?;
+                Ok(ASTNode::TypeDeclaration {
                     name,
                     subtype,
                     generics,
-                }
+                    docs: Docs::default(),
+                })
             }
             Some(Token::Semicolon) => {
                 let subtype = SubType::Empty;
-                self.consume_a_semicolon();
-                ASTNode::TypeDeclaration {
+                self.consume_a_semicolon()?;
+                Ok(ASTNode::TypeDeclaration {
                     name,
                     subtype,
                     generics,
-                }
+                    docs: Docs::default(),
+                })
             }
-            v => todo!("{v:#?}"),
+            _ => self.unexpected("`=` or `;`"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_subtype(&mut self) -> SubType {
+    pub(crate) fn consume_subtype(&mut self) -> ParseResult<SubType> {
         match self.peek_stream() {
             Some(Token::Ident(_)) => self.consume_named_subtype(),
             Some(Token::LeftParenthesis) => self.consume_union_subtype(),
             Some(Token::LeftCurly) => self.consume_map_subtype(),
-            _ => todo!(),
+            _ => self.unexpected("a type"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_union_subtype(&mut self) -> SubType {
+    pub(crate) fn consume_union_subtype(&mut self) -> ParseResult<SubType> {
         self.advance_stream();
         let mut out = Vec::new();
         loop {
@@ -244,84 +403,87 @@ impl<'this> ASTGenerator<'this> {
                     break;
                 }
                 Some(Token::Ident(_)) | Some(Token::LeftParenthesis) | Some(Token::LeftCurly) => {
-                    out.push(self.consume_subtype());
+                    out.push(self.consume_subtype()?);
                     if self.next_is(Token::Pipe) {
                         self.advance_stream();
                         continue;
                     }
                 }
-                _ => todo!(),
+                _ => return self.unexpected("a type or `)`"),
             }
         }
-        SubType::Union(out)
+        Ok(SubType::Union(out))
     }
 
     #[inline]
-    pub(crate) fn consume_named_subtype(&mut self) -> SubType {
-        let name = self.must_ident();
+    pub(crate) fn consume_named_subtype(&mut self) -> ParseResult<SubType> {
+        let name = self.must_ident()?;
         match self.peek_stream() {
             Some(Token::Semicolon)
             | Some(Token::Pipe)
             | Some(Token::RightParenthesis)
             | Some(Token::Comma)
-            | Some(Token::RightCurly) => SubType::Name(name),
+            | Some(Token::RightCurly) => Ok(SubType::Name(name)),
 
             Some(Token::Colon) => {
                 self.advance_stream();
-                SubType::Named(name, Box::new(self.consume_subtype()))
+                Ok(SubType::Named(name, Box::new(self.consume_subtype()?)))
             }
-            v => unreachable!("{v:#?} btw aa"),
+            _ => self.unexpected("`:`, `|`, `,` or the end of the type"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_map_subtype(&mut self) -> SubType {
+    pub(crate) fn consume_map_subtype(&mut self) -> ParseResult<SubType> {
         self.advance_stream();
-        let mut map = HashMap::new();
+        let mut map = BTreeMap::new();
         loop {
-            match self.advance_stream() {
+            match self.peek_stream().copied() {
                 Some(Token::Ident(name)) => {
+                    self.advance_stream();
                     let string = name
                         .parse_arena(self.loader.load(self.src), self.arena)
                         .to_owned();
-                    self.must(Token::Colon);
-                    map.insert(string, self.consume_subtype());
+                    self.must(Token::Colon)?;
+                    map.insert(string, self.consume_subtype()?);
                     if self.next_is(Token::Comma) {
                         self.advance_stream();
                         continue;
                     }
                 }
                 Some(Token::RightCurly) => {
+                    self.advance_stream();
                     break;
                 }
-                _ => todo!(),
+                _ => return self.unexpected("a field name or `}`"),
             }
         }
-        SubType::Map(map)
+        Ok(SubType::Map(map))
     }
 
     #[inline]
-    pub(crate) fn consume_public_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_public_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let val = self.parse_value();
-        ASTNode::Public(Box::new(val))
+        let val = self.parse_value()?;
+        Ok(ASTNode::Public(Box::new(val)))
     }
 
     #[inline]
-    pub(crate) fn consume_generics(&mut self) -> Generics {
+    pub(crate) fn consume_generics(&mut self) -> ParseResult<Generics> {
         if !self.next_is(Token::LeftSquare) {
-            return Generics(vec![]);
+            return Ok(Generics(vec![]));
         }
-        self.must(Token::LeftSquare);
+        self.must(Token::LeftSquare)?;
         let mut out = Vec::new();
         loop {
-            match self.advance_stream() {
+            match self.peek_stream().copied() {
                 Some(Token::Hash) => {
-                    let name = self.must_ident();
+                    self.advance_stream();
+                    let name = self.must_ident()?;
                     match self.peek_stream() {
                         Some(Token::Colon) => {
                             self.advance_stream();
-                            let traits = self.consume_traits();
+                            let traits = self.consume_traits()?;
                             out.push(Generic {
                                 name,
                                 generictype: GenericType::Lifetime,
@@ -345,54 +507,58 @@ impl<'this> ASTGenerator<'this> {
                             self.advance_stream();
                             break;
                         }
-                        _ => todo!(),
+                        _ => return self.unexpected("`:`, `,` or `]`"),
                     }
                 }
-                Some(Token::Ident(name)) => match self.peek_stream() {
-                    Some(Token::Colon) => {
-                        self.advance_stream();
-                        let traits = self.consume_traits();
-                        out.push(Generic {
-                            name,
-                            generictype: GenericType::Generic,
-                            traits,
-                        });
-                        if self.next_is(Token::Comma) {
+                Some(Token::Ident(name)) => {
+                    self.advance_stream();
+                    match self.peek_stream() {
+                        Some(Token::Colon) => {
+                            self.advance_stream();
+                            let traits = self.consume_traits()?;
+                            out.push(Generic {
+                                name,
+                                generictype: GenericType::Generic,
+                                traits,
+                            });
+                            if self.next_is(Token::Comma) {
+                                self.advance_stream();
+                                continue;
+                            }
+                        }
+                        Some(Token::Comma) => {
                             self.advance_stream();
+                            out.push(Generic {
+                                name,
+                                generictype: GenericType::Generic,
+                                traits: Traits::default(),
+                            });
                             continue;
                         }
+                        Some(Token::RightSquare) => {
+                            self.advance_stream();
+                            break;
+                        }
+                        _ => return self.unexpected("`:`, `,` or `]`"),
                     }
-                    Some(Token::Comma) => {
-                        self.advance_stream();
-                        out.push(Generic {
-                            name,
-                            generictype: GenericType::Generic,
-                            traits: Traits::default(),
-                        });
-                        continue;
-                    }
-                    Some(Token::RightSquare) => {
-                        self.advance_stream();
-                        break;
-                    }
-                    v => todo!("{v:#?}"),
-                },
+                }
                 Some(Token::RightSquare) | Some(Token::RightCurly) => {
+                    self.advance_stream();
                     break;
                 }
-                _ => todo!(),
+                _ => return self.unexpected("a generic parameter or `]`"),
             }
         }
-        Generics(out)
+        Ok(Generics(out))
     }
 
     #[inline]
-    pub(crate) fn consume_traits(&mut self) -> Traits {
+    pub(crate) fn consume_traits(&mut self) -> ParseResult<Traits> {
         let mut out = Vec::new();
         loop {
             match self.peek_stream() {
                 Some(Token::Ident(_)) => {
-                    out.push(self.consume_a_path());
+                    out.push(self.consume_a_path()?);
                     if self.next_is(Token::Plus) {
                         self.advance_stream();
                         continue;
@@ -403,629 +569,1178 @@ impl<'this> ASTGenerator<'this> {
                 Some(Token::Comma) | Some(Token::RightSquare) | Some(Token::LeftCurly) => {
                     break;
                 }
-                v => todo!("{v:#?}"),
+                _ => return self.unexpected("a trait"),
             }
         }
-        Traits(out)
+        Ok(Traits(out))
     }
 
+    /// Parses `function name[generics](params) => type`, shared by function items and component methods.
     #[inline]
-    pub(crate) fn consume_function_keyword(&mut self) -> ASTNode {
-        self.advance_stream();
-        let name = self.must_ident();
-        let generics = self.consume_generics();
-        let params = self.consume_function_parameters();
-        self.must(Token::FatArrow);
-        let out_type = self.consume_a_path();
-        let block = self.consume_function_block();
-        ASTNode::FunctionDeclaration {
+    pub(crate) fn consume_function_signature(&mut self) -> ParseResult<FunctionSignature> {
+        self.must(Token::FunctionKeyword)?;
+        let name = self.must_ident()?;
+        let generics = self.consume_generics()?;
+        let params = self.consume_function_parameters()?;
+        self.must(Token::FatArrow)?;
+        let out_type = self.consume_a_path()?;
+        Ok(FunctionSignature {
+            name,
+            generics,
+            params,
+            out_type,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_function_keyword(&mut self) -> ParseResult<ASTNode> {
+        let FunctionSignature {
+            name,
+            generics,
+            params,
+            out_type,
+        } = self.consume_function_signature()?;
+        let block = self.consume_function_block()?;
+        Ok(ASTNode::FunctionDeclaration {
             name,
             params,
             block,
             out_type,
             generics,
-        }
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_viewport_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_viewport_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let name = self.must_ident();
-        let params = self.consume_function_parameters();
-        let block = self.consume_function_block();
-        ASTNode::ViewportDeclaration {
+        let name = self.must_ident()?;
+        let params = self.consume_function_parameters()?;
+        let block = self.consume_function_block()?;
+        Ok(ASTNode::ViewportDeclaration {
             name,
             params,
             block,
-        }
+            docs: Docs::default(),
+        })
+    }
+
+    /// Parses `static name: type = expression;`, or the same with `const`.
+    #[inline]
+    pub(crate) fn consume_static_keyword(&mut self) -> ParseResult<ASTNode> {
+        let constant = self.next_is(Token::ConstKeyword);
+        self.advance_stream();
+        let name = self.must_ident()?;
+        self.must(Token::Colon)?;
+        let variabletype = self.consume_a_path()?;
+        self.must(Token::EqualSign)?;
+        let expression = self.consume_an_expression()?;
+        self.consume_a_semicolon()?;
+        Ok(ASTNode::StaticVariableDeclaration {
+            name,
+            constant,
+            variabletype,
+            expression,
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
     pub(crate) fn next_is(&mut self, next: Token) -> bool {
-        self.peek_stream() == &Some(next)
+        self.peek_stream() == Some(&next)
     }
 
     #[inline]
-    pub(crate) fn consume_function_parameters(&mut self) -> FunctionDeclarationParameters {
+    pub(crate) fn consume_function_parameters(
+        &mut self,
+    ) -> ParseResult<FunctionDeclarationParameters> {
         let mut vec = Vec::new();
-        self.must(Token::LeftParenthesis);
+        self.must(Token::LeftParenthesis)?;
         loop {
             if self.next_is(Token::RightParenthesis) {
                 self.advance_stream();
                 break;
             }
-            vec.push(self.consume_function_parameter());
+            vec.push(self.consume_function_parameter()?);
             if self.next_is(Token::Comma) {
                 self.advance_stream();
                 continue;
             }
         }
-        FunctionDeclarationParameters(vec)
+        Ok(FunctionDeclarationParameters(vec))
     }
 
     #[inline]
-    pub(crate) fn must_ident(&mut self) -> Span {
-        if let Some(Token::Ident(v)) = self.advance_stream() {
-            return v;
+    pub(crate) fn must_ident(&mut self) -> ParseResult<Span> {
+        if let Some(Token::Ident(v)) = self.peek_stream().copied() {
+            self.advance_stream();
+            return Ok(v);
         }
-        unreachable!()
+        self.unexpected("an identifier")
     }
 
+    /// Returns the span of a string or raw string literal, and whether it is raw.
     #[inline]
-    pub(crate) fn must_string(&mut self) -> Span {
-        if let Some(Token::String(v)) = self.advance_stream() {
-            return v;
+    pub(crate) fn must_string(&mut self) -> ParseResult<(Span, bool)> {
+        match self.peek_stream().copied() {
+            Some(Token::String(v)) => {
+                self.advance_stream();
+                Ok((v, false))
+            }
+            Some(Token::RawString(v)) => {
+                self.advance_stream();
+                Ok((v, true))
+            }
+            _ => self.unexpected("a string literal"),
         }
-        unreachable!()
     }
 
     #[inline]
-    pub(crate) fn consume_function_parameter(&mut self) -> FunctionDeclarationParameter {
-        let name = self.must_ident();
-        self.must(Token::Colon);
-        let arg_type = self.consume_a_path();
+    pub(crate) fn consume_function_parameter(
+        &mut self,
+    ) -> ParseResult<FunctionDeclarationParameter> {
+        let name = self.must_ident()?;
+        self.must(Token::Colon)?;
+        let arg_type = self.consume_a_path()?;
 
-        FunctionDeclarationParameter { name, arg_type }
+        Ok(FunctionDeclarationParameter { name, arg_type })
     }
 
     #[inline]
-    pub(crate) fn must(&mut self, val: Token) {
-        if self.advance_stream() != Some(val) {
-            // TODO : compiler error
-            unreachable!()
+    pub(crate) fn must(&mut self, val: Token) -> ParseResult<()> {
+        if self.next_is(val) {
+            self.advance_stream();
+            return Ok(());
         }
+        self.unexpected(&val.to_string())
     }
 
     #[inline]
-    pub(crate) fn consume_module_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_module_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let mod_path = self.must_ident();
+        let mod_path = self.must_ident()?;
         match self.peek_stream() {
             Some(Token::Semicolon) => self.consume_node_from_fs(mod_path),
             Some(Token::LeftCurly) => self.consume_mod_block(mod_path),
-            _ => {
-                //TODO : compiler error
-                unreachable!()
-            }
+            _ => self.unexpected("`;` or `{`"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_mod_block(&mut self, mod_path: Span) -> ASTNode {
+    pub(crate) fn consume_mod_block(&mut self, mod_path: Span) -> ParseResult<ASTNode> {
         self.advance_stream();
-        let mut nodes = Vec::new();
-        loop {
-            if self.peek_stream() == &Some(Token::RightCurly) {
-                self.advance_stream();
-                break;
-            }
-            nodes.push(self.parse_value());
-        }
-        ASTNode::ModStatement {
+        let tree = self.consume_item_block()?;
+        Ok(ASTNode::ModStatement {
             name: mod_path,
-            tree: ASTTree::new(nodes, self.src),
-        }
+            tree,
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_node_from_fs(&mut self, mod_path: Span) -> ASTNode {
+    pub(crate) fn consume_node_from_fs(&mut self, mod_path: Span) -> ParseResult<ASTNode> {
         let src = self.loader.load(self.src);
         self.advance_stream();
+        let mod_name = mod_path.parse_arena(src, self.arena);
         let path1 = {
             let mut cloned = self.path.clone();
-            cloned.push(format!(
-                "{}/mod.pipec",
-                mod_path.parse_arena(src, self.arena)
-            ));
+            cloned.push(format!("{mod_name}/mod.pipec"));
             cloned
         };
         let path2 = {
             let mut cloned = self.path.clone();
-            cloned.push(format!("{}.pipec", mod_path.parse_arena(src, self.arena)));
+            cloned.push(format!("{mod_name}.pipec"));
             cloned
         };
+        let label = Label::new(self.src, mod_path);
 
         if self.guard.contains(&path1) || self.guard.contains(&path2) {
-            //TODO : compiler error
-            unreachable!();
+            return self.report(
+                Diagnostic::error(
+                    ErrorKind::RecursiveModule,
+                    format!("module `{mod_name}` includes itself"),
+                )
+                .with_primary(label.with_message("recursive module declared here")),
+            );
         }
         self.guard.push(path1.clone());
         self.guard.push(path2.clone());
 
         if path1.exists() && path2.exists() {
-            // TODO : compiler error
-            unreachable!();
-        }
-        if path1.exists() {
-            let file_id = self.loader.open(&path1, self.arena).unwrap();
-            let file_contents = self.loader.load(file_id);
-            let src = self.arena.take_str_slice(file_contents);
-
-            let mut tokentree = Tokenizer::new(src).tree();
-
-            let ast_generator = ASTGenerator::new(
-                file_id,
-                &mut tokentree,
-                path1,
-                self.arena,
-                self.guard,
-                self.loader,
+            return self.report(
+                Diagnostic::error(
+                    ErrorKind::AmbiguousModuleFile,
+                    format!("file for module `{mod_name}` found at both of its possible paths"),
+                )
+                .with_primary(label)
+                .with_note(format!("found {}", path1.display()))
+                .with_note(format!("found {}", path2.display()))
+                .with_help("delete or rename one of the files"),
             );
-            let tree = ast_generator.tree();
-            return ASTNode::ModStatement {
-                name: mod_path,
-                tree,
-            };
         }
 
-        if path2.exists() {
-            let file_id = self.loader.open(&path2, self.arena).unwrap();
-            let file_contents = self.loader.load(file_id);
-            let src = self.arena.take_str_slice(file_contents);
-
-            let mut tokentree = Tokenizer::new(src).tree();
-
-            let ast_generator = ASTGenerator::new(
-                file_id,
-                &mut tokentree,
-                path1,
-                self.arena,
-                self.guard,
-                self.loader,
+        let path = if path1.exists() {
+            path1.clone()
+        } else if path2.exists() {
+            path2.clone()
+        } else {
+            return self.report(
+                Diagnostic::error(
+                    ErrorKind::ModuleFileNotFound,
+                    format!("file not found for module `{mod_name}`"),
+                )
+                .with_primary(label)
+                .with_help(format!(
+                    "create {} or {}",
+                    path2.display(),
+                    path1.display()
+                )),
             );
-            let tree = ast_generator.tree();
-            return ASTNode::ModStatement {
-                name: mod_path,
-                tree,
-            };
-        }
+        };
 
-        unreachable!()
+        let file_id = match self.loader.open(&path, self.arena) {
+            Ok(v) => v,
+            Err(err) => {
+                return self.report(
+                    Diagnostic::error(
+                        ErrorKind::UnreadableFile,
+                        format!("could not read {}: {err}", path.display()),
+                    )
+                    .with_primary(label),
+                );
+            }
+        };
+        let file_contents = self.loader.load(file_id);
+        let src = self.arena.take_str_slice(file_contents);
+
+        let mut tokentree = Tokenizer::new(src, file_id, self.diagnostics.clone()).tree();
+
+        let ast_generator = ASTGenerator::new(
+            file_id,
+            &mut tokentree,
+            path1,
+            self.arena,
+            self.guard,
+            self.loader,
+            self.diagnostics.clone(),
+        );
+        let tree = ast_generator.tree();
+        Ok(ASTNode::ModStatement {
+            name: mod_path,
+            tree,
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_component_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_component_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
-        if let Some(Token::Ident(v)) = self.advance_stream() {
-            return ASTNode::ComponentDeclaration {
-                name: v,
-                block: self.consume_component_declaration_block(),
-            };
-        }
-        //TODO : compiler error
-        unreachable!();
+        let name = self.must_ident()?;
+        Ok(ASTNode::ComponentDeclaration {
+            name,
+            block: self.consume_component_declaration_block()?,
+            docs: Docs::default(),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_component_declaration_block(&mut self) -> ComponentDeclarationBlock {
-        self.must(Token::LeftCurly);
+    pub(crate) fn consume_component_declaration_block(
+        &mut self,
+    ) -> ParseResult<ComponentDeclarationBlock> {
+        self.must(Token::LeftCurly)?;
         let mut contents = Vec::new();
         loop {
-            let next = self.peek_stream();
-            if next == &Some(Token::RightCurly) {
+            if self.next_is(Token::RightCurly) {
                 self.advance_stream();
                 break;
             }
-            contents.push(self.consume_component_declaration_statement());
+            let depth = self.tokens.depth();
+            let begin = self.node_begin();
+            match self.consume_component_declaration_statement() {
+                Ok(statement) => contents.push(self.spanned(begin, statement)),
+                Err(ParseError) => {
+                    self.synchronize(depth);
+                    // `function` and `const` also start members, anything else means the block was never closed
+                    if self.peek_stream().is_none_or(|v| {
+                        v.is_item_keyword()
+                            && !matches!(v, Token::FunctionKeyword | Token::ConstKeyword)
+                    }) {
+                        return Err(ParseError);
+                    }
+                    contents.push(self.spanned(begin, ComponentDeclarationBlockStatements::Error));
+                }
+            }
         }
 
-        ComponentDeclarationBlock { contents }
+        Ok(ComponentDeclarationBlock { contents })
     }
 
     #[inline]
     pub(crate) fn consume_component_declaration_statement(
         &mut self,
-    ) -> ComponentDeclarationBlockStatements {
-        match self.advance_stream() {
-            Some(Token::FinalKeyword) => self.consume_final_variable_declaration(),
-            Some(Token::RenderKeyword) => self.consume_component_render_block(),
-            _v => {
-                //TODO : compiler error
-                unreachable!();
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        match self.peek_stream() {
+            Some(Token::FinalKeyword) => {
+                self.advance_stream();
+                self.consume_final_variable_declaration()
+            }
+            Some(Token::ConstKeyword) => {
+                self.advance_stream();
+                self.consume_const_variable_declaration()
             }
+            Some(Token::FunctionKeyword) => self.consume_component_function(),
+            Some(Token::PublicKeyword) => {
+                self.advance_stream();
+                self.consume_public_constructor()
+            }
+            Some(Token::RenderKeyword) => {
+                self.advance_stream();
+                self.consume_component_render_block()
+            }
+            _ => self.unexpected("`final`, `const`, `function`, `public`, `render` or `}`"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_component_render_block(&mut self) -> ComponentDeclarationBlockStatements {
-        let block = self.consume_component_render_block_inner();
-        ComponentDeclarationBlockStatements::RenderBlockDeclaration { block }
+    pub(crate) fn consume_const_variable_declaration(
+        &mut self,
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        let variablename = self.must_ident()?;
+        let variabletype = if self.next_is(Token::Colon) {
+            self.advance_stream();
+            Some(self.consume_a_path()?)
+        } else {
+            None
+        };
+        self.must(Token::EqualSign)?;
+        let declarationexpression = self.consume_an_expression()?;
+        self.consume_a_semicolon()?;
+        Ok(
+            ComponentDeclarationBlockStatements::ConstVariableDeclaration {
+                variablename,
+                variabletype,
+                declarationexpression,
+            },
+        )
+    }
+
+    /// Parses a method, which is written like a function item.
+    #[inline]
+    pub(crate) fn consume_component_function(
+        &mut self,
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        let FunctionSignature {
+            name,
+            generics,
+            params,
+            out_type,
+        } = self.consume_function_signature()?;
+        let block = self.consume_function_block()?;
+        Ok(ComponentDeclarationBlockStatements::FunctionDeclaration {
+            name,
+            generics,
+            params,
+            out_type,
+            block,
+        })
+    }
+
+    /// Parses `public(params) { .. }`, the constructor returns the component so it has no return type.
+    #[inline]
+    pub(crate) fn consume_public_constructor(
+        &mut self,
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        let params = self.consume_function_parameters()?;
+        let block = self.consume_function_block()?;
+        Ok(ComponentDeclarationBlockStatements::PublicConstructor { params, block })
+    }
+
+    #[inline]
+    pub(crate) fn consume_component_render_block(
+        &mut self,
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        let block = self.consume_component_render_block_inner()?;
+        Ok(ComponentDeclarationBlockStatements::RenderBlockDeclaration { block })
     }
 
     #[inline]
-    pub(crate) fn consume_component_render_block_inner(&mut self) -> RenderBlock {
-        self.must(Token::LeftCurly);
-        let vertices_block = self.consume_vertices_block();
-        let fragments_block = self.consume_fragments_block();
-        self.must(Token::RightCurly);
-        RenderBlock {
+    pub(crate) fn consume_component_render_block_inner(&mut self) -> ParseResult<RenderBlock> {
+        self.must(Token::LeftCurly)?;
+        let vertices_block = self.consume_vertices_block()?;
+        let fragments_block = self.consume_fragments_block()?;
+        self.must(Token::RightCurly)?;
+        Ok(RenderBlock {
             vertices_block,
             fragments_block,
-        }
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_vertices_block(&mut self) -> VerticesBlock {
-        self.must(Token::VerticesKeyword);
-        VerticesBlock {
-            block: self.consume_function_block(),
-        }
+    pub(crate) fn consume_vertices_block(&mut self) -> ParseResult<VerticesBlock> {
+        self.must(Token::VerticesKeyword)?;
+        Ok(VerticesBlock {
+            block: self.consume_function_block()?,
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_fragments_block(&mut self) -> FragmentsBlock {
-        self.must(Token::FragmentsKeyword);
-        FragmentsBlock {
-            block: self.consume_function_block(),
-        }
+    pub(crate) fn consume_fragments_block(&mut self) -> ParseResult<FragmentsBlock> {
+        self.must(Token::FragmentsKeyword)?;
+        Ok(FragmentsBlock {
+            block: self.consume_function_block()?,
+        })
     }
 
     #[inline]
     pub(crate) fn consume_final_variable_declaration(
         &mut self,
-    ) -> ComponentDeclarationBlockStatements {
-        let variablename = self.must_ident();
+    ) -> ParseResult<ComponentDeclarationBlockStatements> {
+        let variablename = self.must_ident()?;
         let variabletype: Option<Path>;
-        let declarationexpression: Option<Expression>;
-        match self.advance_stream() {
+        let declarationexpression: Option<Spanned<Expression>>;
+        match self.peek_stream() {
             Some(Token::Colon) => {
-                variabletype = Some(self.consume_a_path());
-                if let Some(Token::EqualSign) = self.peek_stream() {
-                    declarationexpression = Some(self.consume_an_expression());
+                self.advance_stream();
+                variabletype = Some(self.consume_a_path()?);
+                if self.next_is(Token::EqualSign) {
+                    self.advance_stream();
+                    declarationexpression = Some(self.consume_an_expression()?);
                 } else {
                     declarationexpression = None;
                 }
             }
             Some(Token::EqualSign) => {
+                self.advance_stream();
                 variabletype = None;
-                declarationexpression = Some(self.consume_an_expression());
-            }
-            _v => {
-                //TODO : compile error
-                unreachable!();
+                declarationexpression = Some(self.consume_an_expression()?);
             }
+            _ => return self.unexpected("`:` or `=`"),
         }
-        self.consume_a_semicolon();
-        ComponentDeclarationBlockStatements::FinalVariableDeclaration {
-            variablename,
-            variabletype,
-            declarationexpression,
-        }
+        self.consume_a_semicolon()?;
+        Ok(
+            ComponentDeclarationBlockStatements::FinalVariableDeclaration {
+                variablename,
+                variabletype,
+                declarationexpression,
+            },
+        )
     }
 
     #[inline]
-    pub(crate) fn consume_function_block(&mut self) -> Block {
-        self.must(Token::LeftCurly);
+    pub(crate) fn consume_function_block(&mut self) -> ParseResult<Block> {
+        self.must(Token::LeftCurly)?;
         let mut block = Vec::new();
         loop {
-            let next = self.peek_stream();
-            if next == &Some(Token::RightCurly) {
+            if self.next_is(Token::RightCurly) {
                 self.advance_stream();
                 break;
             }
-            block.push(self.consume_a_block_statement());
+            if let Some(Token::DocComment(_)) = self.peek_stream() {
+                let begin = self.node_begin();
+                self.consume_outer_docs();
+                let span = Span {
+                    begin,
+                    end: self.tokens.last_span().end,
+                };
+                self.unused_docs(span);
+                continue;
+            }
+            let depth = self.tokens.depth();
+            let begin = self.node_begin();
+            match self.consume_a_block_statement() {
+                Ok(v) => block.push(v),
+                Err(ParseError) => {
+                    self.synchronize(depth);
+                    // the block was never closed, let the item this block belongs to recover instead
+                    if self.peek_stream().is_none_or(Token::is_item_keyword) {
+                        return Err(ParseError);
+                    }
+                    let statement = FunctionBlockStatements::ExpressionStatement {
+                        hidden: true,
+                        expression: self.spanned(begin, Expression::Error),
+                    };
+                    block.push(self.spanned(begin, statement));
+                }
+            }
         }
-        Block(block)
+        Ok(Block(block))
     }
 
     #[inline]
-    pub(crate) fn consume_a_block_statement(&mut self) -> FunctionBlockStatements {
-        match self.peek_stream() {
+    pub(crate) fn consume_a_block_statement(
+        &mut self,
+    ) -> ParseResult<Spanned<FunctionBlockStatements>> {
+        let begin = self.node_begin();
+        let statement = match self.peek_stream() {
             Some(v) => match v {
                 Token::MutableKeyword => self.consume_mutable_variable_declaration(),
                 Token::ImmutableKeyword => self.consume_immutable_variable_declaration(),
                 Token::ExportKeyword => self.consume_export_declaration(),
                 Token::RenderKeyword => self.consume_render_block(),
+                Token::IfKeyword => self.consume_if_statement(),
+                Token::WhileKeyword => self.consume_while_statement(),
+                Token::LoopKeyword => self.consume_loop_statement(),
+                Token::ForKeyword => self.consume_for_statement(),
+                Token::BreakKeyword => self.consume_break_statement(),
+                Token::ContinueKeyword => self.consume_continue_statement(),
+                Token::ReturnKeyword => self.consume_return_statement(),
                 _ => self.consume_expression_statement(),
             },
-            None => {
-                // TODO : compiler error
-                unreachable!()
-            }
-        }
+            None => self.unexpected("a statement or `}`"),
+        }?;
+        Ok(self.spanned(begin, statement))
     }
     #[inline]
-    pub(crate) fn consume_render_block(&mut self) -> FunctionBlockStatements {
+    pub(crate) fn consume_render_block(&mut self) -> ParseResult<FunctionBlockStatements> {
         self.advance_stream();
-        FunctionBlockStatements::RenderBlock {
-            block: self.consume_function_block(),
+        Ok(FunctionBlockStatements::RenderBlock {
+            block: self.consume_function_block()?,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_expression_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        let expression = self.consume_an_expression()?;
+        let mut hidden = false;
+        if self.next_is(Token::Semicolon) {
+            hidden = true;
+            self.advance_stream();
         }
+        Ok(FunctionBlockStatements::ExpressionStatement { expression, hidden })
     }
 
+    /// An `if` starting a statement ends at its last `}`, so `if a {} -b;` isn't taken as a subtraction.
     #[inline]
-    pub(crate) fn consume_expression_statement(&mut self) -> FunctionBlockStatements {
-        let expression = self.consume_an_expression();
+    pub(crate) fn consume_if_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        let begin = self.node_begin();
+        let expression = self.consume_if_expression()?;
+        let expression = self.spanned(begin, expression);
         let mut hidden = false;
         if self.next_is(Token::Semicolon) {
             hidden = true;
             self.advance_stream();
         }
-        FunctionBlockStatements::ExpressionStatement { expression, hidden }
+        Ok(FunctionBlockStatements::ExpressionStatement { expression, hidden })
     }
 
     #[inline]
-    pub(crate) fn consume_export_declaration(&mut self) -> FunctionBlockStatements {
-        let src = self.loader.load(self.src);
+    pub(crate) fn consume_while_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
         self.advance_stream();
-        let exporting: Exported = match self.advance_stream() {
-            Some(Token::Hash) => match self.advance_stream() {
-                Some(Token::Ident(name)) => match name.parse_arena(src, self.arena) {
-                    "col" => Exported::ColorBuiltin,
-                    "pos" => Exported::PositionBuiltin,
-                    _ => {
-                        // TODO : compiler error
-                        unreachable!()
+        let condition = self.consume_an_expression()?;
+        Ok(FunctionBlockStatements::WhileStatement {
+            condition,
+            block: self.consume_function_block()?,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_loop_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        self.advance_stream();
+        Ok(FunctionBlockStatements::LoopStatement {
+            block: self.consume_function_block()?,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_for_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        self.advance_stream();
+        let item = self.must_ident()?;
+        self.must(Token::InKeyword)?;
+        let iterable = self.consume_an_expression()?;
+        Ok(FunctionBlockStatements::ForStatement {
+            item,
+            iterable,
+            block: self.consume_function_block()?,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_break_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        self.advance_stream();
+        self.consume_statement_end()?;
+        Ok(FunctionBlockStatements::BreakStatement)
+    }
+
+    #[inline]
+    pub(crate) fn consume_continue_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        self.advance_stream();
+        self.consume_statement_end()?;
+        Ok(FunctionBlockStatements::ContinueStatement)
+    }
+
+    #[inline]
+    pub(crate) fn consume_return_statement(&mut self) -> ParseResult<FunctionBlockStatements> {
+        self.advance_stream();
+        let value = match self.peek_stream() {
+            Some(Token::Semicolon | Token::RightCurly) => None,
+            _ => Some(self.consume_an_expression()?),
+        };
+        self.consume_statement_end()?;
+        Ok(FunctionBlockStatements::ReturnStatement { value })
+    }
+
+    /// Consumes the `;` ending a statement, which may be left out before the `}` closing the block.
+    #[inline]
+    pub(crate) fn consume_statement_end(&mut self) -> ParseResult<()> {
+        if self.next_is(Token::RightCurly) {
+            return Ok(());
+        }
+        self.consume_a_semicolon()
+    }
+
+    #[inline]
+    pub(crate) fn consume_export_declaration(&mut self) -> ParseResult<FunctionBlockStatements> {
+        let src = self.loader.load(self.src);
+        self.advance_stream();
+        let exporting: Exported = match self.peek_stream().copied() {
+            Some(Token::Hash) => {
+                self.advance_stream();
+                let name = self.must_ident()?;
+                match name.parse_arena(src, self.arena) {
+                    "col" => Exported::ColorBuiltin,
+                    "pos" => Exported::PositionBuiltin,
+                    builtin => {
+                        return self.report(
+                            Diagnostic::error(
+                                ErrorKind::UnknownExportBuiltin,
+                                format!("unknown builtin `#{builtin}`"),
+                            )
+                            .with_primary(Label::new(self.src, name))
+                            .with_help("the builtins that can be exported are `#col` and `#pos`"),
+                        );
                     }
-                },
-                _ => {
-                    // TODO : compiler error
-                    unreachable!()
                 }
-            },
-            Some(Token::Ident(name)) => Exported::Custom(name),
-            _ => {
-                //TODO : compiler error
-                unreachable!()
             }
+            Some(Token::Ident(name)) => {
+                self.advance_stream();
+                Exported::Custom(name)
+            }
+            _ => return self.unexpected("an identifier or a builtin"),
         };
         let decl_type: Option<Path>;
-        let decl_expr: Expression;
-        match self.advance_stream() {
+        let decl_expr: Spanned<Expression>;
+        match self.peek_stream() {
             Some(Token::EqualSign) => {
+                self.advance_stream();
                 decl_type = None;
-                decl_expr = self.consume_an_expression();
-                self.consume_a_semicolon();
-                FunctionBlockStatements::ExportDeclaration {
+                decl_expr = self.consume_an_expression()?;
+                self.consume_a_semicolon()?;
+                Ok(FunctionBlockStatements::ExportDeclaration {
                     exporting,
                     exporttype: decl_type,
                     expression: decl_expr,
-                }
+                })
             }
             Some(Token::Colon) => {
-                decl_type = Some(self.consume_a_path());
-                if self.advance_stream() == Some(Token::EqualSign) {
-                    decl_expr = self.consume_an_expression();
-                    self.consume_a_semicolon();
-                    return FunctionBlockStatements::ExportDeclaration {
-                        exporting,
-                        exporttype: decl_type,
-                        expression: decl_expr,
-                    };
-                }
-                // TODO : compiler error
-                unreachable!();
-            }
-            _ => {
-                //TODO : compiler error
-                unreachable!();
+                self.advance_stream();
+                decl_type = Some(self.consume_a_path()?);
+                self.must(Token::EqualSign)?;
+                decl_expr = self.consume_an_expression()?;
+                self.consume_a_semicolon()?;
+                Ok(FunctionBlockStatements::ExportDeclaration {
+                    exporting,
+                    exporttype: decl_type,
+                    expression: decl_expr,
+                })
             }
+            _ => self.unexpected("`:` or `=`"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_mutable_variable_declaration(&mut self) -> FunctionBlockStatements {
+    pub(crate) fn consume_mutable_variable_declaration(
+        &mut self,
+    ) -> ParseResult<FunctionBlockStatements> {
         self.advance_stream();
         // mutable x : u32 = 0;
-        let varname = self.must_ident();
+        let varname = self.must_ident()?;
         let vartype: Option<Path>;
-        let declexpr: Option<Expression>;
-        match self.advance_stream() {
+        let declexpr: Option<Spanned<Expression>>;
+        match self.peek_stream() {
             Some(Token::Colon) => {
-                vartype = Some(self.consume_a_path());
-                match self.advance_stream() {
-                    Some(Token::EqualSign) => {
-                        declexpr = Some(self.consume_an_expression());
-                    }
-                    _anything_else => {
-                        //TODO : compiler error
-                        unreachable!();
-                    }
-                }
+                self.advance_stream();
+                vartype = Some(self.consume_a_path()?);
+                self.must(Token::EqualSign)?;
+                declexpr = Some(self.consume_an_expression()?);
             }
 
             Some(Token::EqualSign) => {
-                declexpr = Some(self.consume_an_expression());
+                self.advance_stream();
+                declexpr = Some(self.consume_an_expression()?);
                 vartype = None;
             }
 
-            _ => {
-                //TODO : compiler error
-                unreachable!()
-            }
+            _ => return self.unexpected("`:` or `=`"),
         }
-        self.consume_a_semicolon();
+        self.consume_a_semicolon()?;
 
-        FunctionBlockStatements::MutableVariableDeclaration {
+        Ok(FunctionBlockStatements::MutableVariableDeclaration {
             variablename: varname,
             variabletype: vartype,
             declarationexpression: declexpr,
-        }
+        })
         // TODO : update this function
     }
     #[inline]
-    pub(crate) fn consume_immutable_variable_declaration(&mut self) -> FunctionBlockStatements {
+    pub(crate) fn consume_immutable_variable_declaration(
+        &mut self,
+    ) -> ParseResult<FunctionBlockStatements> {
         self.advance_stream();
         // mutable x : u32 = 0;
-        let varname = self.must_ident();
+        let varname = self.must_ident()?;
         let vartype: Option<Path>;
-        let declexpr: Option<Expression>;
-        match self.advance_stream() {
+        let declexpr: Option<Spanned<Expression>>;
+        match self.peek_stream() {
             Some(Token::Colon) => {
-                vartype = Some(self.consume_a_path());
-                match self.advance_stream() {
-                    Some(Token::EqualSign) => {
-                        declexpr = Some(self.consume_an_expression());
-                    }
-                    _anything_else => {
-                        //TODO : compiler error
-                        unreachable!();
-                    }
-                }
+                self.advance_stream();
+                vartype = Some(self.consume_a_path()?);
+                self.must(Token::EqualSign)?;
+                declexpr = Some(self.consume_an_expression()?);
             }
 
             Some(Token::EqualSign) => {
-                declexpr = Some(self.consume_an_expression());
+                self.advance_stream();
+                declexpr = Some(self.consume_an_expression()?);
                 vartype = None;
             }
 
-            _ => {
-                //TODO : compiler error
-                unreachable!()
-            }
+            _ => return self.unexpected("`:` or `=`"),
         }
-        self.consume_a_semicolon();
+        self.consume_a_semicolon()?;
 
-        FunctionBlockStatements::ImmutableVariableDeclaration {
+        Ok(FunctionBlockStatements::ImmutableVariableDeclaration {
             variablename: varname,
             variabletype: vartype,
             declarationexpression: declexpr,
-        }
+        })
         // TODO : update this function
     }
 
     #[inline]
-    pub(crate) fn consume_a_semicolon(&mut self) {
-        let peek = self.peek_stream();
-        if peek == &Some(Token::Semicolon) {
+    pub(crate) fn consume_a_semicolon(&mut self) -> ParseResult<()> {
+        if self.next_is(Token::Semicolon) {
             self.advance_stream();
-            return;
+            return Ok(());
         }
-        //TODO : compiler error
-        todo!();
+        let end = self.tokens.last_span().end;
+        let diagnostic = self
+            .unexpected_diagnostic("`;`")
+            .with_suggestion(Suggestion::new(
+                self.src,
+                Span { begin: end, end },
+                ";",
+                "add a `;` after the previous token",
+            ));
+        self.report(diagnostic)
     }
 
     #[inline]
-    pub(crate) fn consume_an_expression(&mut self) -> Expression {
-        let out = match self.peek_stream() {
+    pub(crate) fn consume_an_expression(&mut self) -> ParseResult<Spanned<Expression>> {
+        self.consume_expression_with(0)
+    }
+
+    /// Parses an expression whose binary operators all bind at least as tight as `min_power`.
+    /// See [`Precedence`] for the binding powers.
+    pub(crate) fn consume_expression_with(
+        &mut self,
+        min_power: u8,
+    ) -> ParseResult<Spanned<Expression>> {
+        let mut lhs = self.consume_postfix_expression()?;
+        let mut previous = None;
+        while let Some(token) = self.peek_stream().copied() {
+            let (optype, precedence) = match (token, binary_operator(token)) {
+                (_, Some((optype, precedence))) => (Some(optype), precedence),
+                (Token::DoubleDot | Token::DoubleDotEqual, None) => (None, Precedence::Range),
+                _ => break,
+            };
+            let (left, right) = precedence.binding_power();
+            if left < min_power {
+                break;
+            }
+            if !precedence.is_associative() && previous == Some(precedence) {
+                self.chained_operator(precedence, lhs.span);
+            }
+            previous = Some(precedence);
+            self.advance_stream();
+            let rhs = self.consume_expression_with(right)?;
+            let begin = lhs.span.begin;
+            let node = match optype {
+                Some(optype) => Expression::BinaryOpExpression {
+                    optype,
+                    lhs: Box::new(lhs),
+                    rhs: Box::new(rhs),
+                },
+                None => Expression::RangeExpression {
+                    start: Box::new(lhs),
+                    end: Box::new(rhs),
+                    inclusive: token == Token::DoubleDotEqual,
+                },
+            };
+            lhs = self.spanned(begin, node);
+        }
+        Ok(lhs)
+    }
+
+    /// Reports a non associative operator following `lhs`, which is made of an operator of the same precedence.
+    /// The expression is still built as if it was written `(lhs) op rhs` so parsing can go on.
+    pub(crate) fn chained_operator(&mut self, precedence: Precedence, lhs: Span) {
+        let (kind, help) = match precedence {
+            Precedence::Range => ("range", "a range has a single `..` or `..=`"),
+            _ => (
+                "comparison",
+                "split the comparison with `&&`, eg. `a < b && b < c`",
+            ),
+        };
+        let operator = self.tokens.peek_span();
+        self.diagnostics.emit(
+            Diagnostic::error(
+                ErrorKind::ChainedOperator,
+                format!("{kind} operators cannot be chained"),
+            )
+            .with_primary(Label::new(self.src, operator).with_message(format!("second {kind}")))
+            .with_label(Label::new(self.src, lhs).with_message(format!("this is already a {kind}")))
+            .with_help(help),
+        );
+    }
+
+    #[inline]
+    pub(crate) fn consume_primary_expression(&mut self) -> ParseResult<Spanned<Expression>> {
+        let begin = self.node_begin();
+        let expression = match self.peek_stream() {
             Some(Token::Digit { .. }) => self.consume_number_expression(),
-            Some(Token::String(_)) => self.consume_string_expression(),
+            Some(Token::String(_) | Token::RawString(_)) => self.consume_string_expression(),
+            Some(Token::Char(_)) => self.consume_char_expression(),
+            Some(Token::TrueKeyword | Token::FalseKeyword) => self.consume_bool_expression(),
             Some(Token::LeftParenthesis) => self.consume_tuple_expression(),
             Some(Token::LeftSquare) => self.consume_list_expression(),
             Some(Token::Tilde) => self.consume_tilde_expression(),
+            Some(
+                Token::Minus
+                | Token::ExclamationMark
+                | Token::Asterisk
+                | Token::Ampersand
+                | Token::And,
+            ) => self.consume_unary_expression(),
             Some(Token::Ident(_)) => self.consume_path_expression(),
             Some(Token::RequiredKeyword) => self.consume_required_expression(),
             Some(Token::SwitchKeyword) => self.consume_switch_expression(),
+            Some(Token::IfKeyword) => self.consume_if_expression(),
 
-            _v => {
-                //TODO : compiler error
-                unreachable!();
-            }
-        };
-        self.check_expression_rhs(out)
-    }
-
-    #[inline]
-    pub(crate) fn check_expression_rhs(&mut self, input: Expression) -> Expression {
-        let exprtype = match self.peek_stream() {
-            Some(Token::Plus) => Some(BinaryOpType::Add),
-            Some(Token::Minus) => Some(BinaryOpType::Subtract),
-            Some(Token::Asterisk) => Some(BinaryOpType::Multiply),
-            Some(Token::Slash) => Some(BinaryOpType::Divide),
-            Some(Token::PlusEqual) => Some(BinaryOpType::AddEqual),
-            Some(Token::MinusEqual) => Some(BinaryOpType::SubtractEqual),
-            Some(Token::AsteriskEqual) => Some(BinaryOpType::MultiplyEqual),
-            Some(Token::SlashEqual) => Some(BinaryOpType::DivideEqual),
-            Some(Token::ModEqual) => Some(BinaryOpType::ModEqual),
-            _ => None,
-        };
-        if let Some(v) = exprtype {
-            self.advance_stream();
-            let rhs_expr = self.consume_an_expression();
+            _ => self.unexpected("an expression"),
+        }?;
+        Ok(self.spanned(begin, expression))
+    }
 
-            return Expression::BinaryOpExpression {
-                optype: v,
-                lhs: Box::new(input),
-                rhs: Box::new(rhs_expr),
+    #[inline]
+    pub(crate) fn consume_unary_expression(&mut self) -> ParseResult<Expression> {
+        let begin = self.node_begin();
+        let token = self.advance_stream();
+        let op = match token {
+            Some(Token::Minus) => UnaryOpType::Negate,
+            Some(Token::ExclamationMark) => UnaryOpType::Not,
+            Some(Token::Asterisk) => UnaryOpType::Dereference,
+            _ => UnaryOpType::Reference,
+        };
+        let (_, right) = Precedence::Prefix.binding_power();
+        let mut value = self.consume_expression_with(right)?;
+        // `&&x` is lexed as the `&&` operator, in prefix position it is a reference to a reference
+        if token == Some(Token::And) {
+            let inner = Expression::UnaryExpression {
+                op: UnaryOpType::Reference,
+                value: Box::new(value),
             };
+            value = self.spanned(begin + 1, inner);
         }
-        input
+        Ok(Expression::UnaryExpression {
+            op,
+            value: Box::new(value),
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_switch_expression(&mut self) -> Expression {
+    pub(crate) fn consume_if_expression(&mut self) -> ParseResult<Expression> {
+        self.must(Token::IfKeyword)?;
+        let condition = self.consume_an_expression()?;
+        let block = self.consume_function_block()?;
+        let otherwise = if self.next_is(Token::ElseKeyword) {
+            self.advance_stream();
+            if self.next_is(Token::IfKeyword) {
+                let begin = self.node_begin();
+                let expression = self.consume_if_expression()?;
+                Some(ElseBranch::If(Box::new(self.spanned(begin, expression))))
+            } else {
+                Some(ElseBranch::Block(self.consume_function_block()?))
+            }
+        } else {
+            None
+        };
+        Ok(Expression::IfExpression {
+            condition: Box::new(condition),
+            block,
+            otherwise,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_switch_expression(&mut self) -> ParseResult<Expression> {
         self.advance_stream();
-        let expression = self.consume_an_expression();
+        let expression = self.consume_an_expression()?;
         let predicate = Box::new(expression);
-        Expression::SwitchExpression {
+        Ok(Expression::SwitchExpression {
             predicate,
-            block: self.consume_switch_block(),
-        }
+            block: self.consume_switch_block()?,
+        })
     }
 
     #[inline]
-    pub(crate) fn consume_switch_block(&mut self) -> SwitchExpressionBlock {
-        self.must(Token::LeftCurly);
+    pub(crate) fn consume_switch_block(&mut self) -> ParseResult<SwitchExpressionBlock> {
+        self.must(Token::LeftCurly)?;
         let mut out = Vec::new();
         loop {
             if self.next_is(Token::RightCurly) {
                 self.advance_stream();
                 break;
             }
-            out.push(self.consume_switch_arm());
+            out.push(self.consume_switch_arm()?);
             if self.next_is(Token::Comma) {
                 self.advance_stream();
                 continue;
             }
         }
-        SwitchExpressionBlock(out)
+        Ok(SwitchExpressionBlock(out))
     }
 
     #[inline]
-    pub(crate) fn consume_switch_arm(&mut self) -> SwitchArm {
-        let expr = self.consume_an_expression();
+    pub(crate) fn consume_switch_arm(&mut self) -> ParseResult<SwitchArm> {
+        let expr = self.consume_an_expression()?;
         let lhs = Box::new(expr);
-        self.must(Token::ThinArrow);
-        let expr = self.consume_an_expression();
+        self.must(Token::ThinArrow)?;
+        let expr = self.consume_an_expression()?;
         let rhs = Box::new(expr);
-        SwitchArm { lhs, rhs }
+        Ok(SwitchArm { lhs, rhs })
     }
 
     #[inline]
-    pub(crate) fn consume_required_expression(&mut self) -> Expression {
+    pub(crate) fn consume_required_expression(&mut self) -> ParseResult<Expression> {
         self.advance_stream();
-        let expr = self.consume_an_expression();
+        let expr = self.consume_an_expression()?;
         let value = Box::new(expr);
-        Expression::RequiredExpression { value }
+        Ok(Expression::RequiredExpression { value })
     }
 
+    /// Parses a path in an expression. A `[...]` after a name is only taken as the generics of the
+    /// name when a `(` or `\` follows it and it holds nothing but names, like in `make[u32](..)`,
+    /// otherwise it indexes the path, like in `verts[3]`.
     #[inline]
-    pub(crate) fn consume_path_expression(&mut self) -> Expression {
+    pub(crate) fn consume_path_expression(&mut self) -> ParseResult<Expression> {
+        let begin = self.node_begin();
+        let mut nodes = Vec::new();
+        loop {
+            let name = self.must_ident()?;
+            let mut generics = Generics(vec![]);
+            if self.next_is(Token::LeftSquare) {
+                let (values, comma) = self.consume_bracket_expressions()?;
+                match self.generics_before_call(&values, true) {
+                    Some(v) => generics = v,
+                    None => {
+                        nodes.push(PathNode::Singly { name, generics });
+                        let value = Spanned::new(
+                            Expression::PathExpression { value: Path(nodes) },
+                            Span {
+                                begin,
+                                end: name.end,
+                            },
+                            self.src,
+                        );
+                        return self.index_expression(value, values, comma);
+                    }
+                }
+            }
+            nodes.push(PathNode::Singly { name, generics });
+            if self.next_is(Token::Backslash) {
+                self.advance_stream();
+                continue;
+            }
+            return Ok(Expression::PathExpression { value: Path(nodes) });
+        }
+    }
+
+    /// Parses an expression followed by any amount of calls, method calls, field accesses and indexing.
+    #[inline]
+    pub(crate) fn consume_postfix_expression(&mut self) -> ParseResult<Spanned<Expression>> {
+        let mut out = self.consume_primary_expression()?;
+        let begin = out.span.begin;
+        loop {
+            match self.peek_stream() {
+                Some(Token::LeftParenthesis) => {
+                    let arguments = self.consume_call_arguments()?;
+                    let node = Expression::CallExpression {
+                        function: Box::new(out),
+                        arguments,
+                    };
+                    out = self.spanned(begin, node);
+                }
+                Some(Token::LeftSquare) => {
+                    let (values, comma) = self.consume_bracket_expressions()?;
+                    let node = self.index_expression(out, values, comma)?;
+                    out = self.spanned(begin, node);
+                }
+                Some(Token::Dot) => {
+                    self.advance_stream();
+                    let name = self.must_ident()?;
+                    let mut generics = Generics(vec![]);
+                    if self.next_is(Token::LeftSquare) {
+                        let (values, comma) = self.consume_bracket_expressions()?;
+                        match self.generics_before_call(&values, false) {
+                            Some(v) => generics = v,
+                            None => {
+                                let value = Spanned::new(
+                                    Expression::FieldAccessExpression {
+                                        value: Box::new(out),
+                                        field: name,
+                                    },
+                                    Span {
+                                        begin,
+                                        end: name.end,
+                                    },
+                                    self.src,
+                                );
+                                let node = self.index_expression(value, values, comma)?;
+                                out = self.spanned(begin, node);
+                                continue;
+                            }
+                        }
+                    }
+                    let node = if self.next_is(Token::LeftParenthesis) {
+                        Expression::MethodCallExpression {
+                            receiver: Box::new(out),
+                            method: name,
+                            generics,
+                            arguments: self.consume_call_arguments()?,
+                        }
+                    } else {
+                        Expression::FieldAccessExpression {
+                            value: Box::new(out),
+                            field: name,
+                        }
+                    };
+                    out = self.spanned(begin, node);
+                }
+                _ => return Ok(out),
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn consume_call_arguments(&mut self) -> ParseResult<Vec<Spanned<Expression>>> {
+        self.must(Token::LeftParenthesis)?;
+        let mut out = Vec::new();
+        loop {
+            if self.next_is(Token::RightParenthesis) {
+                self.advance_stream();
+                return Ok(out);
+            }
+            out.push(self.consume_an_expression()?);
+            match self.peek_stream() {
+                Some(Token::Comma) => {
+                    self.advance_stream();
+                }
+                Some(Token::RightParenthesis) => {}
+                _ => return self.unexpected("`,` or `)`"),
+            }
+        }
+    }
+
+    /// Parses the expressions between `[` and `]`, also returning the span of the first `,` if any.
+    #[inline]
+    pub(crate) fn consume_bracket_expressions(
+        &mut self,
+    ) -> ParseResult<(Vec<Spanned<Expression>>, Option<Span>)> {
+        self.must(Token::LeftSquare)?;
+        let mut out = Vec::new();
+        let mut comma = None;
+        loop {
+            if self.next_is(Token::RightSquare) {
+                self.advance_stream();
+                return Ok((out, comma));
+            }
+            out.push(self.consume_an_expression()?);
+            match self.peek_stream() {
+                Some(Token::Comma) => {
+                    self.advance_stream();
+                    comma.get_or_insert(self.tokens.last_span());
+                }
+                Some(Token::RightSquare) => {}
+                _ => return self.unexpected("`,` or `]`"),
+            }
+        }
+    }
+
+    /// Turns the contents of a `[...]` into generics when the next token shows they belong to a call.
+    /// Paths also allow generics before a `\`, as in `Vec[u32]\new()`.
+    pub(crate) fn generics_before_call(
+        &mut self,
+        values: &[Spanned<Expression>],
+        in_path: bool,
+    ) -> Option<Generics> {
         match self.peek_stream() {
-            Some(Token::Ident(_)) => Expression::PathExpression {
-                value: self.consume_a_path(),
-            },
-            _v => {
-                //TODO : compile error
-                unreachable!()
+            Some(Token::LeftParenthesis) => {}
+            Some(Token::Backslash) if in_path => {}
+            _ => return None,
+        }
+        let mut out = Vec::new();
+        for value in values {
+            match &value.node {
+                Expression::PathExpression { value: Path(nodes) } => match nodes.as_slice() {
+                    [PathNode::Singly { name, generics }] if generics.0.is_empty() => {
+                        out.push(Generic {
+                            name: *name,
+                            generictype: GenericType::Generic,
+                            traits: Traits::default(),
+                        })
+                    }
+                    _ => return None,
+                },
+                _ => return None,
             }
         }
+        Some(Generics(out))
     }
+
+    pub(crate) fn index_expression(
+        &mut self,
+        value: Spanned<Expression>,
+        mut values: Vec<Spanned<Expression>>,
+        comma: Option<Span>,
+    ) -> ParseResult<Expression> {
+        if values.len() == 1 {
+            return Ok(Expression::IndexExpression {
+                value: Box::new(value),
+                index: Box::new(values.remove(0)),
+            });
+        }
+        let diagnostic = match comma {
+            Some(span) => Diagnostic::error(ErrorKind::UnexpectedToken, "expected `]`, found `,`")
+                .with_primary(Label::new(self.src, span).with_message("expected `]`"))
+                .with_help("indexing takes a single expression"),
+            None => Diagnostic::error(
+                ErrorKind::UnexpectedToken,
+                "expected an expression, found `]`",
+            )
+            .with_primary(
+                Label::new(self.src, self.tokens.last_span())
+                    .with_message("expected an expression"),
+            ),
+        };
+        self.report(diagnostic)
+    }
+
     #[inline]
-    pub(crate) fn consume_list_expression(&mut self) -> Expression {
+    pub(crate) fn consume_list_expression(&mut self) -> ParseResult<Expression> {
         self.advance_stream();
         let mut exprs = Vec::new();
         loop {
-            exprs.push(self.consume_an_expression());
+            exprs.push(self.consume_an_expression()?);
 
             let next = self.peek_stream();
             match next {
@@ -1035,37 +1750,59 @@ impl<'this> ASTGenerator<'this> {
                 }
                 Some(Token::RightSquare) => {
                     self.advance_stream();
-                    return Expression::ListExpression { values: exprs };
-                }
-                _ => {
-                    //TODO : compiler error
-                    unreachable!();
+                    return Ok(Expression::ListExpression { values: exprs });
                 }
+                _ => return self.unexpected("`,` or `]`"),
             }
         }
     }
     #[inline]
-    pub(crate) fn consume_tilde_expression(&mut self) -> Expression {
+    pub(crate) fn consume_tilde_expression(&mut self) -> ParseResult<Expression> {
         self.advance_stream();
-        let expr = self.consume_an_expression();
-        Expression::TildeExpression {
+        let expr = self.consume_an_expression()?;
+        Ok(Expression::TildeExpression {
             value: Box::new(expr),
+        })
+    }
+
+    #[inline]
+    pub(crate) fn consume_string_expression(&mut self) -> ParseResult<Expression> {
+        let (value, raw) = self.must_string()?;
+        Ok(Expression::StringExpression { value, raw })
+    }
+
+    #[inline]
+    pub(crate) fn consume_char_expression(&mut self) -> ParseResult<Expression> {
+        match self.peek_stream().copied() {
+            Some(Token::Char(value)) => {
+                self.advance_stream();
+                Ok(Expression::CharExpression { value })
+            }
+            _ => self.unexpected("a character literal"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_string_expression(&mut self) -> Expression {
-        Expression::PathExpression {
-            value: self.consume_a_path(),
+    pub(crate) fn consume_bool_expression(&mut self) -> ParseResult<Expression> {
+        match self.peek_stream() {
+            Some(Token::TrueKeyword) => {
+                self.advance_stream();
+                Ok(Expression::BoolExpression { value: true })
+            }
+            Some(Token::FalseKeyword) => {
+                self.advance_stream();
+                Ok(Expression::BoolExpression { value: false })
+            }
+            _ => self.unexpected("`true` or `false`"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_tuple_expression(&mut self) -> Expression {
+    pub(crate) fn consume_tuple_expression(&mut self) -> ParseResult<Expression> {
         self.advance_stream();
         let mut values = Vec::new();
         loop {
-            values.push(self.consume_an_expression());
+            values.push(self.consume_an_expression()?);
 
             let next = self.peek_stream();
             match next {
@@ -1075,41 +1812,44 @@ impl<'this> ASTGenerator<'this> {
                 }
                 Some(Token::RightParenthesis) => {
                     self.advance_stream();
-                    return Expression::TupleExpression { values };
-                }
-                _v => {
-                    //TODO : compiler error
-                    unreachable!();
+                    return Ok(Expression::TupleExpression { values });
                 }
+                _ => return self.unexpected("`,` or `)`"),
             }
         }
     }
 
     #[inline]
-    pub(crate) fn consume_number_expression(&mut self) -> Expression {
-        match self.advance_stream() {
+    pub(crate) fn consume_number_expression(&mut self) -> ParseResult<Expression> {
+        match self.peek_stream().copied() {
             Some(Token::Digit {
                 val: value,
                 digittype,
-            }) => Expression::NumberExpression { value, digittype },
-            _v => {
-                //TODO : compile error
-                unreachable!()
+            }) => {
+                self.advance_stream();
+                Ok(Expression::NumberExpression { value, digittype })
             }
+            _ => self.unexpected("a number"),
         }
     }
 
     #[inline]
-    pub(crate) fn consume_using_keyword(&mut self) -> ASTNode {
+    pub(crate) fn consume_using_keyword(&mut self) -> ParseResult<ASTNode> {
         self.advance_stream();
 
-        let using = self.consume_a_path();
-        self.consume_a_semicolon();
-        ASTNode::UsingStatement { using }
+        if !matches!(
+            self.peek_stream(),
+            Some(Token::Ident(_)) | Some(Token::LeftParenthesis)
+        ) {
+            return self.unexpected("a path");
+        }
+        let using = self.consume_a_path()?;
+        self.consume_a_semicolon()?;
+        Ok(ASTNode::UsingStatement { using })
     }
 
     #[inline]
-    fn consume_a_path(&mut self) -> Path {
+    fn consume_a_path(&mut self) -> ParseResult<Path> {
         let mut out = Vec::new();
         loop {
             let next = self.peek_stream();
@@ -1117,7 +1857,7 @@ impl<'this> ASTGenerator<'this> {
                 Some(Token::Ident(v)) => {
                     let name = *v;
                     self.advance_stream();
-                    let generics = self.consume_generics();
+                    let generics = self.consume_generics()?;
                     out.push(PathNode::Singly { name, generics });
                     if self.next_is(Token::Backslash) {
                         self.advance_stream();
@@ -1130,7 +1870,7 @@ impl<'this> ASTGenerator<'this> {
                     self.advance_stream();
                     let mut vals = Vec::new();
                     loop {
-                        vals.push(self.consume_a_path());
+                        vals.push(self.consume_a_path()?);
                         if self.next_is(Token::Comma) {
                             self.advance_stream();
                             continue;
@@ -1139,14 +1879,36 @@ impl<'this> ASTGenerator<'this> {
                         }
                     }
                     out.push(PathNode::Multi(vals));
-                    self.must(Token::RightParenthesis);
+                    self.must(Token::RightParenthesis)?;
                 }
                 _ => {
                     break;
                 }
             }
         }
-        Path(out)
+        Ok(Path(out))
+    }
+}
+
+/// A node together with the full extent of the source it was parsed from.
+/// Derefs to the node, so its fields can be read as if it wasn't wrapped.
+#[derive(Debug, Clone, Hash)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+    pub file: FileId,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span, file: FileId) -> Self {
+        Self { node, span, file }
+    }
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.node
     }
 }
 
@@ -1168,7 +1930,9 @@ pub enum FunctionNodeParams {
 
 #[derive(Debug, Clone)]
 pub enum ASTNode {
+    /// The `function main` a binary starts at, only produced by the GST.
     MainFunction {
+        name: Span,
         block: Block,
     },
     FunctionDeclaration {
@@ -1177,35 +1941,49 @@ pub enum ASTNode {
         params: FunctionDeclarationParameters,
         block: Block,
         out_type: Path,
+        docs: Docs,
     },
     ViewportDeclaration {
         name: Span,
         params: FunctionDeclarationParameters,
         block: Block,
+        docs: Docs,
     },
 
-    StaticVariableDeclaration, // TODO
+    /// `static` or `const` when `constant` is set, the expression has to be evaluable at compile time.
+    StaticVariableDeclaration {
+        name: Span,
+        constant: bool,
+        variabletype: Path,
+        expression: Spanned<Expression>,
+        docs: Docs,
+    },
     ComponentDeclaration {
         name: Span,
         block: ComponentDeclarationBlock,
+        docs: Docs,
     },
     UsingStatement {
         using: Path,
     },
+    /// The `//!` comments of the module are in its tree.
     ModStatement {
         name: Span,
         tree: ASTTree,
+        docs: Docs,
     },
     TypeDeclaration {
         name: Span,
         generics: Generics,
         subtype: SubType,
+        docs: Docs,
     },
     TraitDeclaration {
         name: Span,
         generics: Generics,
         supertraits: Traits,
         tree: ASTTree,
+        docs: Docs,
     },
     ImplementBlock {
         generics: Generics,
@@ -1213,20 +1991,70 @@ pub enum ASTNode {
         implementor: Path,
         block: ASTTree,
     },
-    Public(Box<Self>),
-    Attributed(Vec<Attribute>, Box<Self>),
+    Public(Box<Spanned<Self>>),
+    Attributed(Vec<Attribute>, Box<Spanned<Self>>),
+    /// Placeholder for an item that failed to parse.
+    Error,
     EOF,
 }
 
+impl ASTNode {
+    /// Attaches `///` comments to the declaration, looking through `public` and attributes.
+    /// Returns false if the item is not one that can be documented.
+    pub(crate) fn document(&mut self, input: Docs) -> bool {
+        match self {
+            ASTNode::FunctionDeclaration { docs, .. }
+            | ASTNode::ViewportDeclaration { docs, .. }
+            | ASTNode::StaticVariableDeclaration { docs, .. }
+            | ASTNode::ComponentDeclaration { docs, .. }
+            | ASTNode::ModStatement { docs, .. }
+            | ASTNode::TypeDeclaration { docs, .. }
+            | ASTNode::TraitDeclaration { docs, .. } => {
+                *docs = input;
+                true
+            }
+            ASTNode::Public(node) | ASTNode::Attributed(_, node) => node.node.document(input),
+            _ => false,
+        }
+    }
+}
+
+/// The lines of the `///` or `//!` comments documenting an item, each span covers what follows the `///` or `//!`.
+#[derive(Debug, Clone, Hash, Default)]
+pub struct Docs(pub Vec<Span>);
+
+impl Docs {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Joins the lines, without the single space commonly written after `///`.
+    pub fn text(&self, src: &str) -> String {
+        let lines: Vec<&str> = self
+            .0
+            .iter()
+            .map(|v| {
+                let line = v.parse_str(src).trim_end_matches('\r');
+                line.strip_prefix(' ').unwrap_or(line)
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Attribute {
-    LanguageAttribute(Span),
+    /// `raw` is set when the name is written as a raw string.
+    LanguageAttribute {
+        name: Span,
+        raw: bool,
+    },
     Inline,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 pub enum SubType {
-    Map(HashMap<String, Self>),
+    Map(BTreeMap<String, Self>),
     Name(Span),
     Named(Span, Box<Self>),
     Union(Vec<Self>),
@@ -1234,9 +2062,8 @@ pub enum SubType {
 }
 
 #[derive(Debug, Clone)]
-#[allow(unused)]
 pub struct ComponentDeclarationBlock {
-    contents: Vec<ComponentDeclarationBlockStatements>,
+    pub contents: Vec<Spanned<ComponentDeclarationBlockStatements>>,
 }
 
 #[derive(Debug, Clone)]
@@ -1244,18 +2071,38 @@ pub enum ComponentDeclarationBlockStatements {
     FinalVariableDeclaration {
         variablename: Span,
         variabletype: Option<Path>,
-        declarationexpression: Option<Expression>,
+        declarationexpression: Option<Spanned<Expression>>,
+    },
+    ConstVariableDeclaration {
+        variablename: Span,
+        variabletype: Option<Path>,
+        declarationexpression: Spanned<Expression>,
     },
-    ConstVariableDeclaration,
     FunctionDeclaration {
+        name: Span,
+        generics: Generics,
+        params: FunctionDeclarationParameters,
+        out_type: Path,
         block: Block,
     },
     RenderBlockDeclaration {
         block: RenderBlock,
     },
     PublicConstructor {
-        expression: Expression,
+        params: FunctionDeclarationParameters,
+        block: Block,
     },
+    /// A member that failed to parse, its diagnostic has already been reported.
+    Error,
+}
+
+/// Everything of a function declaration before its block.
+#[derive(Debug, Clone)]
+pub struct FunctionSignature {
+    pub name: Span,
+    pub generics: Generics,
+    pub params: FunctionDeclarationParameters,
+    pub out_type: Path,
 }
 
 #[derive(Debug, Clone)]
@@ -1282,25 +2129,42 @@ pub enum FunctionBlockStatements {
     MutableVariableDeclaration {
         variablename: Span,
         variabletype: Option<Path>,
-        declarationexpression: Option<Expression>,
+        declarationexpression: Option<Spanned<Expression>>,
     },
     ImmutableVariableDeclaration {
         variablename: Span,
         variabletype: Option<Path>,
-        declarationexpression: Option<Expression>,
+        declarationexpression: Option<Spanned<Expression>>,
     },
     ExpressionStatement {
         hidden: bool,
-        expression: Expression,
+        expression: Spanned<Expression>,
     },
     ExportDeclaration {
         exporting: Exported,
         exporttype: Option<Path>,
-        expression: Expression,
+        expression: Spanned<Expression>,
     },
     RenderBlock {
         block: Block,
     },
+    WhileStatement {
+        condition: Spanned<Expression>,
+        block: Block,
+    },
+    LoopStatement {
+        block: Block,
+    },
+    ForStatement {
+        item: Span,
+        iterable: Spanned<Expression>,
+        block: Block,
+    },
+    BreakStatement,
+    ContinueStatement,
+    ReturnStatement {
+        value: Option<Spanned<Expression>>,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Hash)]
@@ -1316,55 +2180,238 @@ pub enum Expression {
         value: Span,
         digittype: DigitType,
     },
+    /// The span covers the source between the quotes, see [`crate::tokenizer::literal::decode_char`].
+    CharExpression {
+        value: Span,
+    },
+    /// The span covers the source between the quotes, see [`crate::tokenizer::literal::decode_string_literal`].
+    /// `raw` is set for `r"..."`, whose contents are not escaped.
+    StringExpression {
+        value: Span,
+        raw: bool,
+    },
+    BoolExpression {
+        value: bool,
+    },
     PathExpression {
         value: Path,
     },
     TupleExpression {
-        values: Vec<Self>,
+        values: Vec<Spanned<Self>>,
     },
     ListExpression {
-        values: Vec<Self>,
+        values: Vec<Spanned<Self>>,
     },
     BinaryOpExpression {
         optype: BinaryOpType,
-        lhs: Box<Self>,
-        rhs: Box<Self>,
+        lhs: Box<Spanned<Self>>,
+        rhs: Box<Spanned<Self>>,
+    },
+    UnaryExpression {
+        op: UnaryOpType,
+        value: Box<Spanned<Self>>,
+    },
+    CallExpression {
+        function: Box<Spanned<Self>>,
+        arguments: Vec<Spanned<Self>>,
+    },
+    MethodCallExpression {
+        receiver: Box<Spanned<Self>>,
+        method: Span,
+        generics: Generics,
+        arguments: Vec<Spanned<Self>>,
+    },
+    FieldAccessExpression {
+        value: Box<Spanned<Self>>,
+        field: Span,
+    },
+    IndexExpression {
+        value: Box<Spanned<Self>>,
+        index: Box<Spanned<Self>>,
+    },
+    /// `start..end`, or `start..=end` when inclusive.
+    RangeExpression {
+        start: Box<Spanned<Self>>,
+        end: Box<Spanned<Self>>,
+        inclusive: bool,
     },
     TildeExpression {
-        value: Box<Self>,
+        value: Box<Spanned<Self>>,
     },
     RequiredExpression {
-        value: Box<Self>,
+        value: Box<Spanned<Self>>,
     },
     SwitchExpression {
-        predicate: Box<Self>,
+        predicate: Box<Spanned<Self>>,
         block: SwitchExpressionBlock,
     },
+    IfExpression {
+        condition: Box<Spanned<Self>>,
+        block: Block,
+        otherwise: Option<ElseBranch>,
+    },
+    /// Placeholder for an expression or statement that failed to parse.
+    Error,
 }
 
+/// What follows the `else` of an `if`, either another `if` or a block.
 #[derive(Debug, Clone, Hash)]
-#[allow(unused)]
-pub struct SwitchExpressionBlock(Vec<SwitchArm>);
+pub enum ElseBranch {
+    If(Box<Spanned<Expression>>),
+    Block(Block),
+}
+
+#[derive(Debug, Clone, Hash)]
+pub struct SwitchExpressionBlock(pub Vec<SwitchArm>);
 
 #[derive(Debug, Clone, Hash)]
-#[allow(unused)]
 pub struct SwitchArm {
-    lhs: Box<Expression>,
-    rhs: Box<Expression>,
+    pub lhs: Box<Spanned<Expression>>,
+    pub rhs: Box<Spanned<Expression>>,
+}
+
+/// How tightly the binary operators bind, from the loosest to the tightest.
+///
+/// | Precedence       | Operators                       | Associativity |
+/// |------------------|---------------------------------|---------------|
+/// | `Assignment`     | `=` `+=` `-=` `*=` `/=` `%=`    | right         |
+/// | `Range`          | `..` `..=`                      | none          |
+/// | `LogicalOr`      | `\|\|`                          | left          |
+/// | `LogicalAnd`     | `&&`                            | left          |
+/// | `Comparison`     | `==` `!=` `<` `<=` `>` `>=`     | none          |
+/// | `BitOr`          | `\|`                            | left          |
+/// | `BitXor`         | `^`                             | left          |
+/// | `BitAnd`         | `&`                             | left          |
+/// | `Additive`       | `+` `-`                         | left          |
+/// | `Multiplicative` | `*` `/` `%`                     | left          |
+/// | `Prefix`         | unary `-` `!` `*` `&`           | right         |
+///
+/// So `a - b - c` parses as `(a - b) - c`, `a * b + c` as `(a * b) + c`
+/// and `a = b += c` as `a = (b += c)`, while `a < b < c` and `a..b..c` are reported as errors.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, PartialOrd, Ord, Hash)]
+pub enum Precedence {
+    Assignment = 1,
+    Range,
+    LogicalOr,
+    LogicalAnd,
+    Comparison,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Additive,
+    Multiplicative,
+    Prefix,
+}
+
+impl Precedence {
+    /// Whether operators of this precedence may follow each other without parentheses.
+    pub fn is_associative(self) -> bool {
+        !matches!(self, Precedence::Comparison | Precedence::Range)
+    }
+
+    /// Returns the left and right binding power of an operator with this precedence.
+    /// An operator only takes an operand whose operators bind at least as tight as its right power,
+    /// giving left associative operators the higher right power keeps them from nesting to the right.
+    pub fn binding_power(self) -> (u8, u8) {
+        let power = self as u8 * 2;
+        match self {
+            Precedence::Assignment => (power + 1, power),
+            _ => (power, power + 1),
+        }
+    }
+}
+
+/// Returns the binary operator a token stands for, along with its precedence.
+pub fn binary_operator(token: Token) -> Option<(BinaryOpType, Precedence)> {
+    use BinaryOpType::*;
+    let out = match token {
+        Token::EqualSign => (Assign, Precedence::Assignment),
+        Token::PlusEqual => (AddEqual, Precedence::Assignment),
+        Token::MinusEqual => (SubtractEqual, Precedence::Assignment),
+        Token::AsteriskEqual => (MultiplyEqual, Precedence::Assignment),
+        Token::SlashEqual => (DivideEqual, Precedence::Assignment),
+        Token::ModEqual => (ModEqual, Precedence::Assignment),
+        Token::Or => (Or, Precedence::LogicalOr),
+        Token::And => (And, Precedence::LogicalAnd),
+        Token::EqualTo => (Equal, Precedence::Comparison),
+        Token::NotEqualTo => (NotEqual, Precedence::Comparison),
+        Token::LeftAngle => (LessThan, Precedence::Comparison),
+        Token::LessThanAndEqual => (LessThanOrEqual, Precedence::Comparison),
+        Token::RightAngle => (GreaterThan, Precedence::Comparison),
+        Token::GreaterThanAndEqual => (GreaterThanOrEqual, Precedence::Comparison),
+        Token::Pipe => (BitOr, Precedence::BitOr),
+        Token::Caret => (BitXor, Precedence::BitXor),
+        Token::Ampersand => (BitAnd, Precedence::BitAnd),
+        Token::Plus => (Add, Precedence::Additive),
+        Token::Minus => (Subtract, Precedence::Additive),
+        Token::Asterisk => (Multiply, Precedence::Multiplicative),
+        Token::Slash => (Divide, Precedence::Multiplicative),
+        Token::Modulo => (Mod, Precedence::Multiplicative),
+        _ => return None,
+    };
+    Some(out)
 }
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub enum BinaryOpType {
+    /// +
     Add,
+    /// -
     Subtract,
+    /// *
     Multiply,
+    /// /
     Divide,
+    /// %
     Mod,
+    /// +=
     AddEqual,
+    /// -=
     SubtractEqual,
+    /// *=
     MultiplyEqual,
+    /// /=
     DivideEqual,
+    /// %=
     ModEqual,
+    /// =
+    Assign,
+    /// ==
+    Equal,
+    /// !=
+    NotEqual,
+    /// <
+    LessThan,
+    /// <=
+    LessThanOrEqual,
+    /// >
+    GreaterThan,
+    /// >=
+    GreaterThanOrEqual,
+    /// &&
+    And,
+    /// ||
+    Or,
+    /// &
+    BitAnd,
+    /// |
+    BitOr,
+    /// ^
+    BitXor,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum UnaryOpType {
+    /// -
+    Negate,
+    /// !, the logical not of a boolean and the bitwise not of an integer.
+    /// Overloaded on purpose like in Rust, `~` already starts a [`Expression::TildeExpression`]
+    /// so there is no other token for bitwise not. Which one is meant follows from the type of the operand.
+    Not,
+    /// *
+    Dereference,
+    /// &
+    Reference,
 }
 
 #[derive(Debug)]
@@ -1375,7 +2422,7 @@ pub enum VariableType {
 
 #[derive(Debug, Clone, Hash)]
 #[allow(unused)]
-pub struct Block(Vec<FunctionBlockStatements>);
+pub struct Block(pub Vec<Spanned<FunctionBlockStatements>>);
 
 #[derive(Debug, Clone, Hash)]
 #[allow(unused)]
@@ -1394,14 +2441,14 @@ pub struct Generics(pub Vec<Generic>);
 #[derive(Debug, Clone, Hash)]
 #[allow(unused)]
 pub struct Generic {
-    name: Span,
-    generictype: GenericType,
-    traits: Traits,
+    pub name: Span,
+    pub generictype: GenericType,
+    pub traits: Traits,
 }
 
 #[derive(Debug, Clone, Hash, Default)]
 #[allow(unused)]
-pub struct Traits(Vec<Path>);
+pub struct Traits(pub Vec<Path>);
 
 #[derive(Debug, Clone, Hash)]
 pub enum GenericType {
@@ -0,0 +1,117 @@
+use crate::tokenizer::Token;
+use crate::tokenizer::trivia::LosslessToken;
+use pipec_file_loader::FileId;
+
+/// A concrete syntax tree, the tokens of a file with all of their trivia, grouped by their delimiters.
+/// Unlike the ASTTree nothing of the source is dropped, so tools like the formatter can write it back out.
+#[derive(Debug, Clone)]
+pub struct CSTTree {
+    pub nodes: Vec<CSTNode>,
+    /// Holds the trivia after the last token.
+    pub eof: LosslessToken,
+    pub id: FileId,
+}
+
+#[derive(Debug, Clone)]
+pub enum CSTNode {
+    Token(LosslessToken),
+    /// `( .. )`, `{ .. }` or `[ .. ]`, close is None if the source ends before the group is closed.
+    Delimited {
+        open: LosslessToken,
+        children: Vec<CSTNode>,
+        close: Option<LosslessToken>,
+    },
+}
+
+impl CSTTree {
+    /// Builds the tree out of the tokens of [`crate::tokenizer::Tokenizer::lossless`].
+    /// A closing delimiter that does not match the open group is kept as a plain token.
+    pub fn new(tokens: impl IntoIterator<Item = LosslessToken>, id: FileId) -> Self {
+        let mut groups: Vec<(LosslessToken, Vec<CSTNode>)> = Vec::new();
+        let mut nodes = Vec::new();
+        let mut eof = None;
+        for token in tokens {
+            let closes = match groups.last() {
+                Some((open, _)) => closing_delimiter(open.token) == Some(token.token),
+                None => false,
+            };
+            if closing_delimiter(token.token).is_some() {
+                groups.push((token, Vec::new()));
+            } else if closes {
+                let (open, children) = groups.pop().expect("a group is open");
+                let node = CSTNode::Delimited {
+                    open,
+                    children,
+                    close: Some(token),
+                };
+                push_node(&mut groups, &mut nodes, node);
+            } else if token.token == Token::EOF {
+                eof = Some(token);
+                break;
+            } else {
+                push_node(&mut groups, &mut nodes, CSTNode::Token(token));
+            }
+        }
+        while let Some((open, children)) = groups.pop() {
+            let node = CSTNode::Delimited {
+                open,
+                children,
+                close: None,
+            };
+            push_node(&mut groups, &mut nodes, node);
+        }
+        let eof = eof.expect("a lossless token stream ends with EOF");
+        Self { nodes, eof, id }
+    }
+
+    /// Returns the source the tree was built from, byte for byte.
+    pub fn text(&self, src: &str) -> String {
+        let mut out = String::with_capacity(src.len());
+        for node in &self.nodes {
+            node.write(src, &mut out);
+        }
+        self.eof.write(src, &mut out);
+        out
+    }
+}
+
+impl CSTNode {
+    pub fn write(&self, src: &str, out: &mut String) {
+        match self {
+            CSTNode::Token(token) => token.write(src, out),
+            CSTNode::Delimited {
+                open,
+                children,
+                close,
+            } => {
+                open.write(src, out);
+                for child in children {
+                    child.write(src, out);
+                }
+                if let Some(close) = close {
+                    close.write(src, out);
+                }
+            }
+        }
+    }
+}
+
+fn closing_delimiter(input: Token) -> Option<Token> {
+    match input {
+        Token::LeftParenthesis => Some(Token::RightParenthesis),
+        Token::LeftCurly => Some(Token::RightCurly),
+        Token::LeftSquare => Some(Token::RightSquare),
+        _ => None,
+    }
+}
+
+fn push_node(
+    groups: &mut [(LosslessToken, Vec<CSTNode>)],
+    nodes: &mut Vec<CSTNode>,
+    node: CSTNode,
+) {
+    match groups.last_mut() {
+        Some((_, children)) => children.push(node),
+        None => nodes.push(node),
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 pub mod ast;
+pub mod cst;
 pub mod tokenizer;
 
 pub struct RecursiveGuard(Vec<PathBuf>);
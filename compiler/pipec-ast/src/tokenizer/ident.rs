@@ -0,0 +1,58 @@
+use std::borrow::Cow;
+use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+/// Returns whether an identifier can start with the character, following UAX #31 with `_` added.
+#[inline]
+pub fn is_ident_start(input: char) -> bool {
+    input == '_' || unicode_ident::is_xid_start(input)
+}
+
+#[inline]
+pub fn is_ident_continue(input: char) -> bool {
+    unicode_ident::is_xid_continue(input)
+}
+
+/// Brings an identifier into NFC, so `größe` is the same name whether the `ö` is written
+/// as one character or as an `o` followed by a combining diaeresis.
+/// Only allocates when the identifier isn't in NFC already, which holds for every ASCII name.
+pub fn normalize_ident(input: &str) -> Cow<'_, str> {
+    if input.is_ascii() {
+        return Cow::Borrowed(input);
+    }
+    match is_nfc_quick(input.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(input),
+        IsNormalized::No | IsNormalized::Maybe => Cow::Owned(input.nfc().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ident_chars() {
+        assert!(is_ident_start('_'));
+        assert!(is_ident_start('a'));
+        assert!(is_ident_start('ö'));
+        assert!(is_ident_start('π'));
+        assert!(is_ident_start('名'));
+        assert!(!is_ident_start('1'));
+        assert!(!is_ident_start('$'));
+        assert!(!is_ident_start('\u{0308}'));
+
+        assert!(is_ident_continue('1'));
+        assert!(is_ident_continue('_'));
+        assert!(is_ident_continue('\u{0308}'));
+        assert!(!is_ident_continue('-'));
+        assert!(!is_ident_continue(' '));
+    }
+
+    #[test]
+    fn test_normalize_ident() {
+        assert!(matches!(normalize_ident("size"), Cow::Borrowed("size")));
+        assert!(matches!(normalize_ident("größe"), Cow::Borrowed("größe")));
+        let decomposed = "gro\u{0308}ße";
+        assert_ne!(decomposed, "größe");
+        assert_eq!(normalize_ident(decomposed), "größe");
+    }
+}
@@ -0,0 +1,516 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::Chars;
+
+/// Why an escape sequence inside a string or char literal is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscapeError {
+    /// `\` followed by a character that doesn't start an escape, eg. `\q`.
+    UnknownEscape(char),
+    /// `\` at the very end of the literal.
+    LoneBackslash,
+    /// `\u` not followed by `{`.
+    MissingUnicodeBrace,
+    /// `\u{` never closed by a `}`.
+    UnclosedUnicodeEscape,
+    /// `\u{}` without any digits, or more than six of them.
+    InvalidUnicodeLength,
+    /// `\u{..}` containing something other than hex digits.
+    InvalidUnicodeDigit(char),
+    /// `\u{..}` naming a value that isn't a unicode scalar value, eg. a surrogate.
+    OutOfRangeUnicode(u32),
+}
+
+impl Display for EscapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscapeError::UnknownEscape(v) => {
+                write!(f, "unknown character escape `\\{}`", v.escape_debug())
+            }
+            EscapeError::LoneBackslash => write!(f, "`\\` must be followed by an escape"),
+            EscapeError::MissingUnicodeBrace => write!(f, "expected `{{` after `\\u`"),
+            EscapeError::UnclosedUnicodeEscape => write!(f, "unterminated unicode escape"),
+            EscapeError::InvalidUnicodeLength => {
+                write!(f, "unicode escape must have between 1 and 6 hex digits")
+            }
+            EscapeError::InvalidUnicodeDigit(v) => {
+                write!(
+                    f,
+                    "invalid character `{}` in unicode escape",
+                    v.escape_debug()
+                )
+            }
+            EscapeError::OutOfRangeUnicode(v) => {
+                write!(f, "`{v:X}` is not a valid unicode character")
+            }
+        }
+    }
+}
+
+/// Walks the source of a string or char literal, without its quotes, calling `callback` with the
+/// byte range and value of every character it stands for.
+/// Escapes are `\n \r \t \\ \" \' \0` and `\u{..}` with up to six hex digits.
+pub fn unescape(input: &str, mut callback: impl FnMut(Range<usize>, Result<char, EscapeError>)) {
+    let mut chars = input.chars();
+    loop {
+        let begin = input.len() - chars.as_str().len();
+        let Some(next) = chars.next() else {
+            return;
+        };
+        let value = match next {
+            '\\' => unescape_one(&mut chars),
+            v => Ok(v),
+        };
+        let end = input.len() - chars.as_str().len();
+        callback(begin..end, value);
+    }
+}
+
+fn unescape_one(chars: &mut Chars) -> Result<char, EscapeError> {
+    let out = match chars.next().ok_or(EscapeError::LoneBackslash)? {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '\\' => '\\',
+        '"' => '"',
+        '\'' => '\'',
+        '0' => '\0',
+        'u' => return unescape_unicode(chars),
+        v => return Err(EscapeError::UnknownEscape(v)),
+    };
+    Ok(out)
+}
+
+fn unescape_unicode(chars: &mut Chars) -> Result<char, EscapeError> {
+    if chars.clone().next() != Some('{') {
+        return Err(EscapeError::MissingUnicodeBrace);
+    }
+    chars.next();
+    let mut value: u32 = 0;
+    let mut digits = 0;
+    loop {
+        match chars.next() {
+            Some('}') => break,
+            Some(v) => {
+                let digit = v.to_digit(16).ok_or(EscapeError::InvalidUnicodeDigit(v))?;
+                digits += 1;
+                if digits > 6 {
+                    return Err(EscapeError::InvalidUnicodeLength);
+                }
+                value = value * 16 + digit;
+            }
+            None => return Err(EscapeError::UnclosedUnicodeEscape),
+        }
+    }
+    if digits == 0 {
+        return Err(EscapeError::InvalidUnicodeLength);
+    }
+    char::from_u32(value).ok_or(EscapeError::OutOfRangeUnicode(value))
+}
+
+/// Returns the value of a string literal from its source, without its quotes.
+/// Invalid escapes are left out, they have been reported by the tokenizer already.
+pub fn decode_string(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    unescape(input, |_, value| {
+        if let Ok(v) = value {
+            out.push(v);
+        }
+    });
+    out
+}
+
+/// Returns the value of a string or raw string literal from its source, without its quotes.
+/// The contents of a raw string are its value as they are.
+pub fn decode_string_literal(input: &str, raw: bool) -> Cow<'_, str> {
+    if raw {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(decode_string(input))
+    }
+}
+
+/// Returns the value of a char literal from its source, without its quotes.
+/// This is None when the literal doesn't stand for exactly one valid character.
+pub fn decode_char(input: &str) -> Option<char> {
+    let mut out = Vec::new();
+    unescape(input, |_, value| out.push(value));
+    match out.as_slice() {
+        [Ok(v)] => Some(*v),
+        _ => None,
+    }
+}
+
+/// The type a number literal is given with a suffix, eg. `10unsigned8` or `1.0f32`.
+/// Every suffix has a long and a short spelling, and stands for the builtin of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberSuffix {
+    Integer8,
+    Unsigned8,
+    Float8,
+    Integer16,
+    Unsigned16,
+    Float16,
+    Integer32,
+    Unsigned32,
+    Float32,
+    Integer64,
+    Unsigned64,
+    Float64,
+}
+
+impl NumberSuffix {
+    pub fn from_name(input: &str) -> Option<Self> {
+        use NumberSuffix::*;
+        let out = match input {
+            "integer8" | "i8" => Integer8,
+            "unsigned8" | "u8" => Unsigned8,
+            "float8" | "f8" => Float8,
+            "integer16" | "i16" => Integer16,
+            "unsigned16" | "u16" => Unsigned16,
+            "float16" | "f16" => Float16,
+            "integer32" | "i32" => Integer32,
+            "unsigned32" | "u32" => Unsigned32,
+            "float32" | "f32" => Float32,
+            "integer64" | "i64" => Integer64,
+            "unsigned64" | "u64" => Unsigned64,
+            "float64" | "f64" => Float64,
+            _ => return None,
+        };
+        Some(out)
+    }
+
+    pub fn is_float(&self) -> bool {
+        use NumberSuffix::*;
+        matches!(self, Float8 | Float16 | Float32 | Float64)
+    }
+
+    /// The largest value a literal of this type may be written with.
+    /// Signed integers allow the magnitude of their minimum, so `-128integer8` can be written.
+    fn max_integer(&self) -> u64 {
+        use NumberSuffix::*;
+        match self {
+            Integer8 => 1 << 7,
+            Unsigned8 => u8::MAX as u64,
+            Integer16 => 1 << 15,
+            Unsigned16 => u16::MAX as u64,
+            Integer32 => 1 << 31,
+            Unsigned32 => u32::MAX as u64,
+            Integer64 => 1 << 63,
+            Unsigned64 | Float8 | Float16 | Float32 | Float64 => u64::MAX,
+        }
+    }
+
+    /// The largest finite value of a float type.
+    /// There is no single 8 bit float format, so those are only checked against the 16 bit limit.
+    fn max_float(&self) -> f64 {
+        use NumberSuffix::*;
+        match self {
+            Float8 | Float16 => 65504.0,
+            Float32 => f32::MAX as f64,
+            _ => f64::MAX,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Integer(u64),
+    Float(f64),
+}
+
+/// The decoded value of a number literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberLiteral {
+    pub value: NumberValue,
+    pub suffix: Option<NumberSuffix>,
+}
+
+/// Why a number literal is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberError {
+    /// `0x`, `0b` or `0o` without any digits after it.
+    MissingDigits(u32),
+    /// A digit too large for the radix, eg. the `2` in `0b102`.
+    InvalidDigit(char, u32),
+    /// A suffix that doesn't name a number type.
+    InvalidSuffix(String),
+    /// A float literal with an integer suffix, eg. `1.5unsigned8`.
+    FloatWithIntegerSuffix(NumberSuffix),
+    /// A literal too large for its type, the string is the type.
+    OutOfRange(&'static str),
+}
+
+impl NumberError {
+    /// True for the errors caused by the value of a literal rather than by how it is written.
+    pub fn is_overflow(&self) -> bool {
+        matches!(self, NumberError::OutOfRange(_))
+    }
+}
+
+fn radix_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    }
+}
+
+impl Display for NumberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberError::MissingDigits(radix) => {
+                write!(f, "no digits in {} literal", radix_name(*radix))
+            }
+            NumberError::InvalidDigit(v, radix) => {
+                write!(f, "invalid digit `{v}` in {} literal", radix_name(*radix))
+            }
+            NumberError::InvalidSuffix(v) => write!(f, "invalid suffix `{v}` for number literal"),
+            NumberError::FloatWithIntegerSuffix(v) => write!(
+                f,
+                "float literal can't have the integer suffix {}",
+                type_name(Some(*v), false)
+            ),
+            NumberError::OutOfRange(v) => write!(f, "literal out of range for {v}"),
+        }
+    }
+}
+
+/// Decodes the source of a number literal, checking that its value fits its type.
+///
+/// Literals are written in decimal or, with a `0x`, `0b` or `0o` prefix, in hexadecimal, binary
+/// or octal. Decimal literals may have a fraction and an exponent, eg. `1.0e-3`. Any digit may be
+/// followed by `_` to separate it from the next, and the literal may end with a [`NumberSuffix`].
+/// Unsuffixed literals are checked against `unsigned64` and `float64`.
+pub fn parse_number(input: &str) -> Result<NumberLiteral, NumberError> {
+    let (radix, body) = match input.get(..2) {
+        Some("0x") => (16, &input[2..]),
+        Some("0b") => (2, &input[2..]),
+        Some("0o") => (8, &input[2..]),
+        _ => (10, input),
+    };
+
+    let (digits, suffix) = if radix == 10 {
+        split_decimal(body)
+    } else {
+        let end = body
+            .find(|v: char| {
+                let digit = match radix {
+                    16 => v.is_ascii_hexdigit(),
+                    _ => v.is_ascii_digit(),
+                };
+                !(digit || v == '_')
+            })
+            .unwrap_or(body.len());
+        body.split_at(end)
+    };
+
+    let suffix = match suffix.trim_start_matches('_') {
+        "" => None,
+        v => Some(
+            NumberSuffix::from_name(v).ok_or_else(|| NumberError::InvalidSuffix(v.to_string()))?,
+        ),
+    };
+    let digits: String = digits.chars().filter(|v| *v != '_').collect();
+    if digits.is_empty() {
+        return Err(NumberError::MissingDigits(radix));
+    }
+
+    let is_float = radix == 10 && digits.contains(['.', 'e', 'E']);
+    if is_float || suffix.is_some_and(|v| v.is_float()) {
+        if let Some(suffix) = suffix.filter(|v| !v.is_float()) {
+            return Err(NumberError::FloatWithIntegerSuffix(suffix));
+        }
+        let value: f64 = digits.parse().unwrap_or(f64::INFINITY);
+        let max = suffix.map_or(f64::MAX, |v| v.max_float());
+        if !value.is_finite() || value > max {
+            return Err(NumberError::OutOfRange(type_name(suffix, true)));
+        }
+        return Ok(NumberLiteral {
+            value: NumberValue::Float(value),
+            suffix,
+        });
+    }
+
+    let mut value: u64 = 0;
+    for v in digits.chars() {
+        let digit = v
+            .to_digit(radix)
+            .ok_or(NumberError::InvalidDigit(v, radix))?;
+        value = value
+            .checked_mul(radix as u64)
+            .and_then(|value| value.checked_add(digit as u64))
+            .ok_or(NumberError::OutOfRange(type_name(suffix, false)))?;
+    }
+    if suffix.is_some_and(|v| value > v.max_integer()) {
+        return Err(NumberError::OutOfRange(type_name(suffix, false)));
+    }
+    Ok(NumberLiteral {
+        value: NumberValue::Integer(value),
+        suffix,
+    })
+}
+
+/// Splits a decimal literal into its digits, with any fraction and exponent, and its suffix.
+fn split_decimal(input: &str) -> (&str, &str) {
+    let bytes = input.as_bytes();
+    let digits = |mut index: usize| {
+        while index < bytes.len() && (bytes[index].is_ascii_digit() || bytes[index] == b'_') {
+            index += 1;
+        }
+        index
+    };
+    let mut end = digits(0);
+    if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end = digits(end + 1);
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        if bytes.get(end + 1 + sign).is_some_and(u8::is_ascii_digit) {
+            end = digits(end + 1 + sign);
+        }
+    }
+    input.split_at(end)
+}
+
+fn type_name(suffix: Option<NumberSuffix>, float: bool) -> &'static str {
+    use NumberSuffix::*;
+    match suffix {
+        Some(Integer8) => "`integer8`",
+        Some(Unsigned8) => "`unsigned8`",
+        Some(Float8) => "`float8`",
+        Some(Integer16) => "`integer16`",
+        Some(Unsigned16) => "`unsigned16`",
+        Some(Float16) => "`float16`",
+        Some(Integer32) => "`integer32`",
+        Some(Unsigned32) => "`unsigned32`",
+        Some(Float32) => "`float32`",
+        Some(Integer64) => "`integer64`",
+        Some(Unsigned64) => "`unsigned64`",
+        Some(Float64) => "`float64`",
+        None if float => "`float64`",
+        None => "`unsigned64`",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_string() {
+        assert_eq!(decode_string(r#"say \"hi\""#), "say \"hi\"");
+        assert_eq!(decode_string(r"a\tb\nc\\d\0"), "a\tb\nc\\d\0");
+        assert_eq!(decode_string(r"\u{48}\u{1F600}"), "H\u{1F600}");
+        assert_eq!(decode_string("plain"), "plain");
+    }
+
+    #[test]
+    fn test_decode_char() {
+        assert_eq!(decode_char("a"), Some('a'));
+        assert_eq!(decode_char(r"\n"), Some('\n'));
+        assert_eq!(decode_char(r"\'"), Some('\''));
+        assert_eq!(decode_char(r"\u{e9}"), Some('é'));
+        assert_eq!(decode_char(""), None);
+        assert_eq!(decode_char("ab"), None);
+        assert_eq!(decode_char(r"\q"), None);
+    }
+
+    #[test]
+    fn test_escape_errors() {
+        let errors = |input: &str| {
+            let mut out = Vec::new();
+            unescape(input, |range, value| {
+                if let Err(err) = value {
+                    out.push((range, err));
+                }
+            });
+            out
+        };
+        assert_eq!(errors(r"a\qb"), [(1..3, EscapeError::UnknownEscape('q'))]);
+        assert_eq!(errors("\\"), [(0..1, EscapeError::LoneBackslash)]);
+        assert_eq!(errors(r"\u41"), [(0..2, EscapeError::MissingUnicodeBrace)]);
+        assert_eq!(
+            errors(r"\u{41"),
+            [(0..5, EscapeError::UnclosedUnicodeEscape)]
+        );
+        assert_eq!(errors(r"\u{}"), [(0..4, EscapeError::InvalidUnicodeLength)]);
+        assert_eq!(
+            errors(r"\u{1234567}"),
+            [(0..10, EscapeError::InvalidUnicodeLength)]
+        );
+        assert_eq!(
+            errors(r"\u{4g}"),
+            [(0..5, EscapeError::InvalidUnicodeDigit('g'))]
+        );
+        assert_eq!(
+            errors(r"\u{D800}"),
+            [(0..8, EscapeError::OutOfRangeUnicode(0xD800))]
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let value = |input: &str| parse_number(input).map(|v| v.value);
+        assert_eq!(value("123"), Ok(NumberValue::Integer(123)));
+        assert_eq!(value("1_000_000"), Ok(NumberValue::Integer(1_000_000)));
+        assert_eq!(value("0xFF00FFFF"), Ok(NumberValue::Integer(0xFF00FFFF)));
+        assert_eq!(value("0b1010"), Ok(NumberValue::Integer(10)));
+        assert_eq!(value("0o17"), Ok(NumberValue::Integer(15)));
+        assert_eq!(value("1.5"), Ok(NumberValue::Float(1.5)));
+        assert_eq!(value("1.0e-3"), Ok(NumberValue::Float(1.0e-3)));
+        assert_eq!(value("2E+2"), Ok(NumberValue::Float(200.0)));
+        assert_eq!(value("10f32"), Ok(NumberValue::Float(10.0)));
+        assert_eq!(
+            parse_number("10unsigned8"),
+            Ok(NumberLiteral {
+                value: NumberValue::Integer(10),
+                suffix: Some(NumberSuffix::Unsigned8)
+            })
+        );
+        assert_eq!(
+            parse_number("0xFF_u8").map(|v| v.suffix),
+            Ok(Some(NumberSuffix::Unsigned8))
+        );
+    }
+
+    #[test]
+    fn test_number_errors() {
+        assert_eq!(parse_number("0x"), Err(NumberError::MissingDigits(16)));
+        assert_eq!(
+            parse_number("0b102"),
+            Err(NumberError::InvalidDigit('2', 2))
+        );
+        assert_eq!(parse_number("0o8"), Err(NumberError::InvalidDigit('8', 8)));
+        assert_eq!(
+            parse_number("12abc"),
+            Err(NumberError::InvalidSuffix("abc".to_string()))
+        );
+        assert_eq!(
+            parse_number("1.5u8"),
+            Err(NumberError::FloatWithIntegerSuffix(NumberSuffix::Unsigned8))
+        );
+        assert_eq!(
+            parse_number("256unsigned8"),
+            Err(NumberError::OutOfRange("`unsigned8`"))
+        );
+        assert!(parse_number("255unsigned8").is_ok());
+        assert!(parse_number("128i8").is_ok());
+        assert!(parse_number("129i8").is_err());
+        assert_eq!(
+            parse_number("18446744073709551616"),
+            Err(NumberError::OutOfRange("`unsigned64`"))
+        );
+        assert!(parse_number("18446744073709551615").is_ok());
+        assert_eq!(
+            parse_number("1e39f32"),
+            Err(NumberError::OutOfRange("`float32`"))
+        );
+        assert_eq!(
+            parse_number("1e400"),
+            Err(NumberError::OutOfRange("`float64`"))
+        );
+    }
+}
@@ -1,33 +1,66 @@
+use crate::tokenizer::ident::{is_ident_continue, is_ident_start};
+use crate::tokenizer::literal::{NumberValue, parse_number, unescape};
 use crate::tokenizer::tokentree::TokenTree;
+use crate::tokenizer::trivia::{LosslessTokens, Trivia, TriviaKind};
+use pipec_errors::{Diagnostic, DiagnosticEmitter, ErrorKind, Label};
+use pipec_file_loader::FileId;
 use pipec_span::{Span, SpannedIterator};
+use std::fmt::Display;
+pub mod ident;
+pub mod literal;
 pub mod tokentree;
+pub mod trivia;
 use putbackpeekmore::PutBackPeekMore;
 
 pub struct Tokenizer<'chars> {
     src: &'chars str,
     stream: SpannedIterator<'chars>,
     position: usize,
+    file: FileId,
+    diagnostics: DiagnosticEmitter,
+    token_start: Span,
+    /// The whitespace and comments skipped since the last take, only collected in lossless mode.
+    trivia: Option<Vec<Trivia>>,
 }
 
 impl<'chars> Iterator for Tokenizer<'chars> {
-    type Item = Token;
+    type Item = (Token, Span);
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.consume_next_token();
         if next == Token::EOF {
             return None;
         }
-        Some(next)
+        let mut span = self.token_start;
+        span.end(&self.stream);
+        Some((next, span))
     }
 }
 
 impl<'chars> Tokenizer<'chars> {
-    pub fn new(input: &'chars str) -> Self {
+    pub fn new(input: &'chars str, file: FileId, diagnostics: DiagnosticEmitter) -> Self {
         Self {
             src: input,
             stream: SpannedIterator::new(input),
             position: 0,
+            file,
+            diagnostics,
+            token_start: Span::default(),
+            trivia: None,
         }
     }
+
+    /// Turns the tokenizer into an iterator over every token together with the trivia around it,
+    /// see [`LosslessTokens`].
+    pub fn lossless(mut self) -> LosslessTokens<'chars> {
+        self.trivia = Some(Vec::new());
+        LosslessTokens::new(self)
+    }
+
+    #[inline]
+    pub(crate) fn report(&self, kind: ErrorKind, message: String, span: Span) {
+        self.diagnostics
+            .emit(Diagnostic::error(kind, message).with_primary(Label::new(self.file, span)));
+    }
     pub(crate) fn new_span(&mut self) -> Span {
         self.stream.new_span()
     }
@@ -46,68 +79,105 @@ impl<'chars> Tokenizer<'chars> {
     }
 
     pub fn consume_next_token(&mut self) -> Token {
-        self.consume_comment();
-        let peek = self.peek_stream();
-
-        match peek {
-            Some(next_code_point) => match next_code_point {
-                '(' => self.consume_left_paranthesis(),
-                ')' => self.consume_right_paranthesis(),
-                '{' => self.consume_left_curly(),
-                '}' => self.consume_right_curly(),
-                '[' => self.consume_left_square(),
-                ']' => self.consume_right_square(),
-                '<' => self.consume_left_angle(),
-                '>' => self.consume_right_angle(),
-                '&' => self.consume_ampersand(),
-                '+' => self.consume_plus(),
-                '-' => self.consume_minus(),
-                '/' => match self.consume_slash() {
-                    Some(v) => v,
-                    None => self.consume_next_token(),
+        // invalid characters are reported and skipped until a token starts
+        loop {
+            self.consume_trivia();
+            self.token_start = self.new_span();
+            let peek = *self.peek_stream();
+
+            return match peek {
+                Some(next_code_point) => match next_code_point {
+                    '(' => self.consume_left_paranthesis(),
+                    ')' => self.consume_right_paranthesis(),
+                    '{' => self.consume_left_curly(),
+                    '}' => self.consume_right_curly(),
+                    '[' => self.consume_left_square(),
+                    ']' => self.consume_right_square(),
+                    '<' => self.consume_left_angle(),
+                    '>' => self.consume_right_angle(),
+                    '&' => self.consume_ampersand(),
+                    '+' => self.consume_plus(),
+                    '-' => self.consume_minus(),
+                    '/' if self.doc_comment_start() => self.consume_doc_comment(),
+                    '/' => self.consume_slash(),
+                    '*' => self.consume_asterisk(),
+                    '!' => self.consume_exclamation_mark(),
+                    '?' => self.consume_question_mark(),
+                    '~' => self.consume_tilde(),
+                    '^' => self.consume_caret(),
+                    '@' => self.consume_at_sign(),
+                    '%' => self.consume_modulo(),
+                    '|' => self.consume_pipe(),
+                    ';' => self.consume_semicolon(),
+                    ':' => self.consume_colon(),
+                    '.' => self.consume_dot(),
+                    ',' => self.consume_comma(),
+                    '=' => self.consume_equal_sign(),
+                    '"' => self.consume_string(),
+                    '\'' => self.consume_char(),
+                    'r' if self.raw_string_hashes().is_some() => self.consume_raw_string(),
+                    '#' => self.consume_hash(),
+                    '\\' => self.consume_backslash(),
+                    v if is_ident_start(v) => self.consume_ident_token(),
+                    v if v.is_ascii_digit() => self.consume_digit_token(),
+                    v => {
+                        self.advance_stream();
+                        let mut span = self.token_start;
+                        span.end(&self.stream);
+                        self.report(
+                            ErrorKind::InvalidCharacter,
+                            format!("invalid character `{}` in source", v.escape_debug()),
+                            span,
+                        );
+                        self.push_trivia(TriviaKind::Invalid, span);
+                        continue;
+                    }
                 },
-                '*' => self.consume_asterisk(),
-                '!' => self.consume_exclamation_mark(),
-                '?' => self.consume_question_mark(),
-                '~' => self.consume_tilde(),
-                '^' => self.consume_caret(),
-                '@' => self.consume_at_sign(),
-                '%' => self.consume_modulo(),
-                '|' => self.consume_pipe(),
-                ';' => self.consume_semicolon(),
-                ':' => self.consume_colon(),
-                '.' => self.consume_dot(),
-                ',' => self.consume_comma(),
-                '=' => self.consume_equal_sign(),
-                '"' => self.consume_string(),
-                '#' => self.consume_hash(),
-                '\\' => self.consume_backslash(),
-                v if v.is_ascii_whitespace() => {
-                    self.consume_whitespace();
-                    self.consume_next_token()
+                None => Token::EOF,
+            };
+        }
+    }
+
+    /// Skips every piece of whitespace and every comment in front of the next token.
+    #[inline]
+    pub(crate) fn consume_trivia(&mut self) {
+        loop {
+            if self.doc_comment_start() {
+                return;
+            }
+            let mut span = self.new_span();
+            let kind = match self.peek_stream_value(2) {
+                [Some('/'), Some('/')] => {
+                    self.consume_single_line_comment();
+                    TriviaKind::LineComment
                 }
-                v if v.is_ascii_alphabetic() => self.consume_ident_token(),
-                v if v.is_ascii_digit() => self.consume_digit_token(),
-                _v => {
-                    println!("unexpected token {_v}");
-                    //TODO : compiler error
-                    unreachable!()
+                [Some('/'), Some('*')] => {
+                    self.consume_multi_line_comment();
+                    TriviaKind::BlockComment
                 }
-            },
-            None => Token::EOF,
+                [Some(v), ..] if v.is_ascii_whitespace() => {
+                    self.consume_whitespace();
+                    TriviaKind::Whitespace
+                }
+                _ => return,
+            };
+            span.end(&self.stream);
+            self.push_trivia(kind, span);
         }
     }
 
     #[inline]
-    pub(crate) fn consume_comment(&mut self) {
-        match self.peek_stream_value(2) {
-            [Some('/'), Some('/')] => {
-                self.consume_single_line_comment();
-            }
-            [Some('/'), Some('*')] => {
-                self.consume_multi_line_comment();
-            }
-            _ => {}
+    pub(crate) fn push_trivia(&mut self, kind: TriviaKind, span: Span) {
+        if let Some(trivia) = &mut self.trivia {
+            trivia.push(Trivia { kind, span });
+        }
+    }
+
+    /// Returns the trivia collected since the last call, always empty outside of lossless mode.
+    pub(crate) fn take_trivia(&mut self) -> Vec<Trivia> {
+        match &mut self.trivia {
+            Some(trivia) => std::mem::take(trivia),
+            None => Vec::new(),
         }
     }
 
@@ -203,6 +273,10 @@ impl<'chars> Tokenizer<'chars> {
     #[inline]
     pub(crate) fn consume_modulo(&mut self) -> Token {
         self.advance_stream();
+        if self.peek_stream() == &Some('=') {
+            self.advance_stream();
+            return Token::ModEqual;
+        }
         Token::Modulo
     }
     #[inline]
@@ -222,6 +296,14 @@ impl<'chars> Tokenizer<'chars> {
     #[inline]
     pub(crate) fn consume_dot(&mut self) -> Token {
         self.advance_stream();
+        if self.peek_stream() == &Some('.') {
+            self.advance_stream();
+            if self.peek_stream() == &Some('=') {
+                self.advance_stream();
+                return Token::DoubleDotEqual;
+            }
+            return Token::DoubleDot;
+        }
         Token::Dot
     }
     #[inline]
@@ -297,21 +379,157 @@ impl<'chars> Tokenizer<'chars> {
         self.advance_stream();
         let mut out = self.new_span();
         loop {
-            let next = self.advance_stream();
-            match next {
+            match self.peek_stream() {
                 Some('"') => {
                     out.end(&self.stream);
+                    self.advance_stream();
+                    self.check_escapes(out);
                     return Token::String(out);
                 }
+                Some('\\') => {
+                    // skip the escaped character so `\"` doesn't end the string
+                    self.advance_stream();
+                    self.advance_stream();
+                }
+                Some(_) => {
+                    self.advance_stream();
+                }
                 None => {
-                    // TODO : compiler error
-                    unreachable!()
+                    self.report_unterminated_string();
+                    out.end(&self.stream);
+                    self.check_escapes(out);
+                    return Token::String(out);
+                }
+            }
+        }
+    }
+
+    /// The source that hasn't been consumed yet, for lookahead further than the stream can peek.
+    #[inline]
+    pub(crate) fn rest(&self) -> &'chars str {
+        &self.src[self.stream.index()..]
+    }
+
+    /// Returns the amount of `#` after the `r` starting a raw string, or None if the `r` starts an identifier.
+    #[inline]
+    pub(crate) fn raw_string_hashes(&self) -> Option<usize> {
+        let after = self.rest().strip_prefix('r')?;
+        let hashes = after.bytes().take_while(|v| *v == b'#').count();
+        after[hashes..].starts_with('"').then_some(hashes)
+    }
+
+    /// Consumes `r"..."`, or `r#"..."#` when the string itself contains a `"`.
+    /// Any amount of `#` may be used, the string ends at the first `"` followed by as many `#` as it started with.
+    /// Nothing inside a raw string is escaped.
+    #[inline]
+    pub(crate) fn consume_raw_string(&mut self) -> Token {
+        let hashes = self.raw_string_hashes().unwrap_or_default();
+        for _ in 0..hashes + 2 {
+            self.advance_stream();
+        }
+        let mut out = self.new_span();
+        loop {
+            let closes = self.rest().strip_prefix('"').is_some_and(|v| {
+                v.as_bytes()
+                    .get(..hashes)
+                    .is_some_and(|v| v.iter().all(|v| *v == b'#'))
+            });
+            if closes {
+                out.end(&self.stream);
+                for _ in 0..hashes + 1 {
+                    self.advance_stream();
                 }
-                _ => {}
+                return Token::RawString(out);
+            }
+            if self.advance_stream().is_none() {
+                self.report_unterminated_string();
+                out.end(&self.stream);
+                return Token::RawString(out);
             }
         }
     }
 
+    #[inline]
+    pub(crate) fn report_unterminated_string(&mut self) {
+        let mut span = self.token_start;
+        span.end(&self.stream);
+        self.report(
+            ErrorKind::UnterminatedString,
+            "unterminated string literal".to_string(),
+            span,
+        );
+    }
+
+    /// Consumes a char literal, eg. `'a'` or `'\n'`. The literal ends at the end of the line
+    /// when it isn't closed, so one missing `'` doesn't swallow the rest of the file.
+    #[inline]
+    pub(crate) fn consume_char(&mut self) -> Token {
+        self.advance_stream();
+        let mut out = self.new_span();
+        loop {
+            match self.peek_stream() {
+                Some('\'') => {
+                    out.end(&self.stream);
+                    self.advance_stream();
+                    break;
+                }
+                Some('\\') => {
+                    self.advance_stream();
+                    if self.peek_stream().is_some_and(|v| v != '\n') {
+                        self.advance_stream();
+                    }
+                }
+                Some('\n') | None => {
+                    out.end(&self.stream);
+                    let mut span = self.token_start;
+                    span.end(&self.stream);
+                    self.report(
+                        ErrorKind::InvalidCharLiteral,
+                        "unterminated character literal".to_string(),
+                        span,
+                    );
+                    return Token::Char(out);
+                }
+                Some(_) => {
+                    self.advance_stream();
+                }
+            }
+        }
+
+        let mut span = self.token_start;
+        span.end(&self.stream);
+        let mut count = 0;
+        unescape(out.parse_str(self.src), |_, _| count += 1);
+        match count {
+            0 => self.report(
+                ErrorKind::InvalidCharLiteral,
+                "empty character literal".to_string(),
+                span,
+            ),
+            1 => self.check_escapes(out),
+            _ => self.report(
+                ErrorKind::InvalidCharLiteral,
+                "character literal may only contain one character".to_string(),
+                span,
+            ),
+        }
+        Token::Char(out)
+    }
+
+    /// Reports every invalid escape in the source of a string or char literal.
+    #[inline]
+    pub(crate) fn check_escapes(&self, input: Span) {
+        unescape(input.parse_str(self.src), |range, value| {
+            if let Err(err) = value {
+                let span = Span {
+                    begin: input.begin + range.start,
+                    end: input.begin + range.end,
+                };
+                self.report(ErrorKind::InvalidEscape, err.to_string(), span);
+            }
+        });
+    }
+
     #[inline]
     pub(crate) fn consume_whitespace(&mut self) {
         while let Some(v) = self.peek_stream()
@@ -324,28 +542,70 @@ impl<'chars> Tokenizer<'chars> {
     #[inline]
     pub(crate) fn consume_digit_token(&mut self) -> Token {
         let mut out = self.new_span();
-        let mut digittype = DigitType::Int;
+        let prefixed = matches!(
+            self.peek_stream_value(2),
+            [Some('0'), Some('x' | 'b' | 'o')]
+        );
+        if prefixed {
+            self.advance_stream();
+            self.advance_stream();
+        }
+        let mut float = false;
+        let mut suffix = false;
         loop {
-            let peek = self.peek_stream();
-            if let Some(v) = peek
-                && (v.is_ascii_digit())
-            {
-                self.advance_stream();
-                continue;
-            }
-            if peek == &Some('.') {
-                match digittype {
-                    DigitType::Float => break,
-                    DigitType::Int => {
+            let peek = *self.peek_stream();
+            match peek {
+                Some(v) if v.is_ascii_digit() || v == '_' => {
+                    self.advance_stream();
+                }
+                // `0..n` is a range and `1.abs()` a method call, only a digit after the `.` makes a float
+                Some('.')
+                    if !prefixed
+                        && !float
+                        && !suffix
+                        && self.peek_stream_value(2)[1].is_some_and(|v| v.is_ascii_digit()) =>
+                {
+                    self.advance_stream();
+                    float = true;
+                }
+                Some('e' | 'E') if !prefixed && !suffix => {
+                    self.advance_stream();
+                    float = true;
+                    if matches!(
+                        self.peek_stream_value(2),
+                        [Some('+' | '-'), Some('0'..='9')]
+                    ) {
                         self.advance_stream();
-                        digittype = DigitType::Float;
-                        continue;
                     }
                 }
+                Some(v) if v.is_ascii_alphabetic() => {
+                    self.advance_stream();
+                    suffix = true;
+                }
+                _ => break,
             }
-            break;
         }
         out.end(&self.stream);
+
+        let digittype = match parse_number(out.parse_str(self.src)) {
+            Ok(v) => match v.value {
+                NumberValue::Integer(_) => DigitType::Int,
+                NumberValue::Float(_) => DigitType::Float,
+            },
+            Err(err) => {
+                let kind = if err.is_overflow() {
+                    ErrorKind::NumberOutOfRange
+                } else {
+                    ErrorKind::InvalidNumberLiteral
+                };
+                self.report(kind, err.to_string(), out);
+                if float {
+                    DigitType::Float
+                } else {
+                    DigitType::Int
+                }
+            }
+        };
         Token::Digit {
             val: out,
             digittype,
@@ -353,24 +613,13 @@ impl<'chars> Tokenizer<'chars> {
     }
 
     #[inline]
-    pub(crate) fn consume_slash(&mut self) -> Option<Token> {
+    pub(crate) fn consume_slash(&mut self) -> Token {
         self.advance_stream();
-        match self.peek_stream() {
-            Some('/') => {
-                self.consume_single_line_comment();
-                return None;
-            }
-            Some('*') => {
-                self.consume_multi_line_comment();
-                return None;
-            }
-            Some('=') => {
-                self.advance_stream();
-                return Some(Token::SlashEqual);
-            }
-            _ => {}
+        if self.peek_stream() == &Some('=') {
+            self.advance_stream();
+            return Token::SlashEqual;
         }
-        Some(Token::Slash)
+        Token::Slash
     }
     #[inline]
     pub(crate) fn consume_hash(&mut self) -> Token {
@@ -380,39 +629,96 @@ impl<'chars> Tokenizer<'chars> {
 
     #[inline]
     pub(crate) fn consume_single_line_comment(&mut self) {
-        loop {
-            let next = self.advance_stream();
-            match next {
-                Some('\n') | None => return,
-                _ => {}
-            }
+        // the line break is left for the whitespace after the comment
+        while let Some(v) = self.peek_stream()
+            && *v != '\n'
+        {
+            self.advance_stream();
         }
     }
 
+    /// Returns whether the next characters start a `///` or `//!` comment, `////` is a plain comment.
     #[inline]
-    pub(crate) fn consume_multi_line_comment(&mut self) {
+    pub(crate) fn doc_comment_start(&mut self) -> bool {
+        match self.peek_stream_value(4) {
+            [Some('/'), Some('/'), Some('!'), ..] => true,
+            [Some('/'), Some('/'), Some('/'), Some('/')] => false,
+            [Some('/'), Some('/'), Some('/'), ..] => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn consume_doc_comment(&mut self) -> Token {
         self.advance_stream();
+        self.advance_stream();
+        let inner = self.advance_stream() == Some('!');
+        let mut out = self.new_span();
+        self.consume_single_line_comment();
+        out.end(&self.stream);
+        if inner {
+            Token::InnerDocComment(out)
+        } else {
+            Token::DocComment(out)
+        }
+    }
+
+    /// Consumes a block comment, block comments nest so `/* /* */ */` is a single comment.
+    #[inline]
+    pub(crate) fn consume_multi_line_comment(&mut self) {
+        // the start of every comment not closed yet, the outermost first
+        let mut open = Vec::new();
         loop {
-            let peek = self.peek_stream_value(2);
-            if peek == [Some('*'), Some('/')] {
-                self.advance_stream();
-                self.advance_stream();
-                return;
-            }
-            if peek == [None] {
-                //TODO : compiler error
-                return;
+            match self.peek_stream_value(2) {
+                [Some('/'), Some('*')] => {
+                    let mut span = self.new_span();
+                    self.advance_stream();
+                    self.advance_stream();
+                    span.end(&self.stream);
+                    open.push(span);
+                }
+                [Some('*'), Some('/')] => {
+                    self.advance_stream();
+                    self.advance_stream();
+                    open.pop();
+                    if open.is_empty() {
+                        return;
+                    }
+                }
+                [None, ..] | [] => {
+                    self.report_unterminated_comment(&open);
+                    return;
+                }
+                _ => {
+                    self.advance_stream();
+                }
             }
-            self.advance_stream();
         }
     }
+
+    pub(crate) fn report_unterminated_comment(&mut self, open: &[Span]) {
+        let mut diagnostic =
+            Diagnostic::error(ErrorKind::UnterminatedComment, "unterminated block comment")
+                .with_primary(Label::new(self.file, open[0]).with_message("comment starts here"));
+        if let [_, .., last] = open {
+            diagnostic = diagnostic
+                .with_label(Label::new(self.file, *last).with_message(
+                    "the last nested comment starts here, maybe it was meant to be closed",
+                ))
+                .with_note(format!(
+                    "block comments nest, {} comments are still open at the end of the file",
+                    open.len()
+                ));
+        }
+        self.diagnostics.emit(diagnostic);
+    }
     #[inline]
     pub(crate) fn consume_ident_token(&mut self) -> Token {
         let mut out = self.new_span();
         loop {
             let peek = self.peek_stream();
             if let Some(v) = peek
-                && (v.is_ascii_alphabetic() || v.is_ascii_digit() || *v == '_')
+                && is_ident_continue(*v)
             {
                 self.advance_stream();
                 continue;
@@ -432,6 +738,8 @@ impl<'chars> Tokenizer<'chars> {
             "viewport" => ViewportKeyword,
             "component" => ComponentKeyword,
             "final" => FinalKeyword,
+            "const" => ConstKeyword,
+            "static" => StaticKeyword,
             "render" => RenderKeyword,
             "vertices" => VerticesKeyword,
             "fragments" => FragmentsKeyword,
@@ -447,6 +755,16 @@ impl<'chars> Tokenizer<'chars> {
             "trait" => TraitKeyword,
             "implement" => ImplementKeyword,
             "for" => ForKeyword,
+            "if" => IfKeyword,
+            "else" => ElseKeyword,
+            "while" => WhileKeyword,
+            "loop" => LoopKeyword,
+            "break" => BreakKeyword,
+            "continue" => ContinueKeyword,
+            "return" => ReturnKeyword,
+            "in" => InKeyword,
+            "true" => TrueKeyword,
+            "false" => FalseKeyword,
             _ => Token::Ident(input),
         }
     }
@@ -454,11 +772,13 @@ impl<'chars> Tokenizer<'chars> {
     pub fn tree(self) -> TokenTree<'chars> {
         TokenTree {
             stream: PutBackPeekMore::new(self),
+            last: Span::default(),
+            depth: 0,
         }
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Token {
     /// ==
     EqualTo,
@@ -490,6 +810,10 @@ pub enum Token {
     Comma,
     /// .
     Dot,
+    /// ..
+    DoubleDot,
+    /// ..=
+    DoubleDotEqual,
     /// +
     Plus,
     /// -
@@ -510,7 +834,7 @@ pub enum Token {
     AsteriskEqual,
     /// /=
     SlashEqual,
-    /// /=
+    /// %=
     ModEqual,
     /// !
     ExclamationMark,
@@ -550,6 +874,10 @@ pub enum Token {
     ComponentKeyword,
     /// final
     FinalKeyword,
+    /// const
+    ConstKeyword,
+    /// static
+    StaticKeyword,
     /// render
     RenderKeyword,
     /// vertices
@@ -580,16 +908,155 @@ pub enum Token {
     ImplementKeyword,
     /// for
     ForKeyword,
-    /// 21213
-    Digit { val: Span, digittype: DigitType },
-    /// things_like_this or this_2
+    /// if
+    IfKeyword,
+    /// else
+    ElseKeyword,
+    /// while
+    WhileKeyword,
+    /// loop
+    LoopKeyword,
+    /// break
+    BreakKeyword,
+    /// continue
+    ContinueKeyword,
+    /// return
+    ReturnKeyword,
+    /// in
+    InKeyword,
+    /// true
+    TrueKeyword,
+    /// false
+    FalseKeyword,
+    /// 21213, 0xFF, 1_000, 1.0e-3 or 10unsigned8, see [`literal::parse_number`]
+    Digit {
+        val: Span,
+        digittype: DigitType,
+    },
+    /// things_like_this, this_2, _unused or größe, see [`ident::is_ident_start`]
     Ident(Span),
-    /// "things like this"
+    /// "things like this", the span covers what is between the quotes with its escapes undecoded
     String(Span),
-    /// 'h'    Char(char),
+    /// r"things like this" or r#"things "like" this"#, the span covers what is between the quotes
+    RawString(Span),
+    /// /// documents the item after it, the span covers the rest of the line
+    DocComment(Span),
+    /// //! documents the module it is in, the span covers the rest of the line
+    InnerDocComment(Span),
+    /// 'h', the span covers what is between the quotes with its escapes undecoded
+    Char(Span),
     EOF,
 }
 
+impl Token {
+    /// Returns true for the keywords an item can begin with.
+    pub fn is_item_keyword(&self) -> bool {
+        matches!(
+            self,
+            Token::FunctionKeyword
+                | Token::ViewportKeyword
+                | Token::StaticKeyword
+                | Token::ConstKeyword
+                | Token::ComponentKeyword
+                | Token::ModuleKeyword
+                | Token::TypeKeyword
+                | Token::TraitKeyword
+                | Token::ImplementKeyword
+                | Token::UsingKeyword
+        )
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Token::*;
+        let out = match self {
+            EqualTo => "`==`",
+            NotEqualTo => "`!=`",
+            GreaterThanAndEqual => "`>=`",
+            LessThanAndEqual => "`<=`",
+            And => "`&&`",
+            Or => "`||`",
+            ThinArrow => "`->`",
+            FatArrow => "`=>`",
+            AtSign => "`@`",
+            EqualSign => "`=`",
+            Semicolon => "`;`",
+            Colon => "`:`",
+            DoubleColon => "`::`",
+            Comma => "`,`",
+            Dot => "`.`",
+            DoubleDot => "`..`",
+            DoubleDotEqual => "`..=`",
+            Plus => "`+`",
+            Minus => "`-`",
+            Slash => "`/`",
+            Asterisk => "`*`",
+            Ampersand => "`&`",
+            Modulo => "`%`",
+            PlusEqual => "`+=`",
+            MinusEqual => "`-=`",
+            AsteriskEqual => "`*=`",
+            SlashEqual => "`/=`",
+            ModEqual => "`%=`",
+            ExclamationMark => "`!`",
+            QuestionMark => "`?`",
+            Tilde => "`~`",
+            Caret => "`^`",
+            Pipe => "`|`",
+            LeftParenthesis => "`(`",
+            RightParenthesis => "`)`",
+            LeftSquare => "`[`",
+            RightSquare => "`]`",
+            LeftCurly => "`{`",
+            RightCurly => "`}`",
+            LeftAngle => "`<`",
+            RightAngle => "`>`",
+            Hash => "`#`",
+            Backslash => "`\\`",
+            UsingKeyword => "keyword `using`",
+            ViewportKeyword => "keyword `viewport`",
+            ComponentKeyword => "keyword `component`",
+            FinalKeyword => "keyword `final`",
+            ConstKeyword => "keyword `const`",
+            StaticKeyword => "keyword `static`",
+            RenderKeyword => "keyword `render`",
+            VerticesKeyword => "keyword `vertices`",
+            FragmentsKeyword => "keyword `fragments`",
+            ExportKeyword => "keyword `export`",
+            PublicKeyword => "keyword `public`",
+            RequiredKeyword => "keyword `required`",
+            ModuleKeyword => "keyword `module`",
+            MutableKeyword => "keyword `mutable`",
+            ImmutableKeyword => "keyword `immutable`",
+            FunctionKeyword => "keyword `function`",
+            SwitchKeyword => "keyword `switch`",
+            TypeKeyword => "keyword `type`",
+            TraitKeyword => "keyword `trait`",
+            ImplementKeyword => "keyword `implement`",
+            ForKeyword => "keyword `for`",
+            IfKeyword => "keyword `if`",
+            ElseKeyword => "keyword `else`",
+            WhileKeyword => "keyword `while`",
+            LoopKeyword => "keyword `loop`",
+            BreakKeyword => "keyword `break`",
+            ContinueKeyword => "keyword `continue`",
+            ReturnKeyword => "keyword `return`",
+            InKeyword => "keyword `in`",
+            TrueKeyword => "keyword `true`",
+            FalseKeyword => "keyword `false`",
+            Digit { .. } => "number",
+            Ident(_) => "identifier",
+            String(_) | RawString(_) => "string literal",
+            DocComment(_) => "doc comment",
+            InnerDocComment(_) => "inner doc comment",
+            Char(_) => "character literal",
+            EOF => "end of file",
+        };
+        write!(f, "{out}")
+    }
+}
+
 #[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
 pub enum DigitType {
     Float,
@@ -1,17 +1,49 @@
 use crate::tokenizer::Token;
 use crate::tokenizer::Tokenizer;
+use pipec_span::Span;
 use putbackpeekmore::PutBackPeekMore;
 
 #[derive(Debug)]
 pub struct TokenTree<'toks> {
     pub(crate) stream: PutBackPeekMore<Tokenizer<'toks>, 4>,
+    pub(crate) last: Span,
+    pub(crate) depth: usize,
 }
 
 impl<'toks> TokenTree<'toks> {
     pub fn next_token(&mut self) -> Option<Token> {
-        self.stream.next()
+        let (token, span) = self.stream.next()?;
+        self.last = span;
+        match token {
+            Token::LeftCurly => self.depth += 1,
+            Token::RightCurly => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+        Some(token)
     }
-    pub fn peek(&mut self) -> &Option<Token> {
-        self.stream.peek()
+    pub fn peek(&mut self) -> Option<&Token> {
+        self.stream.peek().as_ref().map(|(token, _)| token)
+    }
+
+    /// Returns the span of the next token.
+    /// At the end of the stream this is an empty span right after the last token.
+    pub fn peek_span(&mut self) -> Span {
+        match self.stream.peek() {
+            Some((_, span)) => *span,
+            None => Span {
+                begin: self.last.end,
+                end: self.last.end,
+            },
+        }
+    }
+
+    /// Returns the span of the last consumed token.
+    pub fn last_span(&self) -> Span {
+        self.last
+    }
+
+    /// Returns how many `{` consumed so far are still unclosed.
+    pub fn depth(&self) -> usize {
+        self.depth
     }
 }
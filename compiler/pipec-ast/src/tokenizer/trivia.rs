@@ -0,0 +1,104 @@
+use crate::tokenizer::{Token, Tokenizer};
+use pipec_span::Span;
+
+/// Source text that belongs to no token, skipped by the parser but kept by the lossless tokenizer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    /// `// ...`, without the line break ending it
+    LineComment,
+    /// `/* ... */`
+    BlockComment,
+    /// A character no token can start with, it has already been reported.
+    Invalid,
+}
+
+impl Trivia {
+    /// Returns whether the trivia ends the line of the token in front of it.
+    pub(crate) fn breaks_line(&self, src: &str) -> bool {
+        self.span.parse_str(src).contains('\n')
+    }
+}
+
+/// A token with the trivia around it. Trivia after a token up to the end of its line is trailing,
+/// everything else is leading trivia of the next token.
+/// The stream always ends with a [`Token::EOF`] holding the trivia at the end of the source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LosslessToken {
+    pub token: Token,
+    pub span: Span,
+    pub leading: Vec<Trivia>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl LosslessToken {
+    /// Writes the token and its trivia out exactly as they are in the source.
+    pub fn write(&self, src: &str, out: &mut String) {
+        for trivia in &self.leading {
+            out.push_str(trivia.span.parse_str(src));
+        }
+        out.push_str(self.span.parse_str(src));
+        for trivia in &self.trailing {
+            out.push_str(trivia.span.parse_str(src));
+        }
+    }
+}
+
+/// The iterator returned by [`Tokenizer::lossless`].
+pub struct LosslessTokens<'chars> {
+    tokenizer: Tokenizer<'chars>,
+    leading: Vec<Trivia>,
+    finished: bool,
+}
+
+impl<'chars> LosslessTokens<'chars> {
+    pub(crate) fn new(tokenizer: Tokenizer<'chars>) -> Self {
+        Self {
+            tokenizer,
+            leading: Vec::new(),
+            finished: false,
+        }
+    }
+}
+
+impl<'chars> Iterator for LosslessTokens<'chars> {
+    type Item = LosslessToken;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let token = self.tokenizer.consume_next_token();
+        let mut span = self.tokenizer.token_start;
+        span.end(&self.tokenizer.stream);
+        let mut leading = std::mem::take(&mut self.leading);
+        leading.extend(self.tokenizer.take_trivia());
+        if token == Token::EOF {
+            self.finished = true;
+            return Some(LosslessToken {
+                token,
+                span,
+                leading,
+                trailing: Vec::new(),
+            });
+        }
+
+        self.tokenizer.consume_trivia();
+        let mut trailing = self.tokenizer.take_trivia();
+        let src = self.tokenizer.src;
+        if let Some(index) = trailing.iter().position(|v| v.breaks_line(src)) {
+            self.leading = trailing.split_off(index);
+        }
+        Some(LosslessToken {
+            token,
+            span,
+            leading,
+            trailing,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Diagnostic, Renderer, Severity};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Collects every diagnostic reported during a compilation.
+/// Cloning an emitter is cheap and the clones share the same diagnostics, so the tokenizer,
+/// the ASTGenerator and the GlobalSymbolTree can all report into one place.
+#[derive(Clone, Default, Debug)]
+pub struct DiagnosticEmitter {
+    diagnostics: Rc<RefCell<Vec<Diagnostic>>>,
+}
+
+impl DiagnosticEmitter {
+    pub fn emit(&self, input: Diagnostic) {
+        self.diagnostics.borrow_mut().push(input);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .borrow()
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.borrow().is_empty()
+    }
+
+    /// Returns a copy of every diagnostic reported so far.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.diagnostics.borrow().clone()
+    }
+
+    /// Removes and returns every diagnostic reported so far.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        std::mem::take(&mut *self.diagnostics.borrow_mut())
+    }
+
+    /// Prints every diagnostic reported so far to stderr.
+    pub fn flush(&self, renderer: &Renderer) {
+        for diagnostic in self.take() {
+            eprintln!("{}", renderer.output(&diagnostic));
+        }
+    }
+}
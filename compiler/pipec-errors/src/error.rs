@@ -1,9 +1,5 @@
-#[allow(dead_code)]
+/// Implemented by every kind of error the compiler is able to report.
 pub trait Error {
-    fn throw(&self) {
-        print!("ERROR: {}", self.name());
-        std::process::exit(1);
-    }
     fn name(&self) -> &'static str;
     fn code(&self) -> u16;
 }
@@ -0,0 +1,207 @@
+use crate::Error;
+
+/// Every error the compiler is able to report, each with a stable numbered code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    UnexpectedToken,
+    UnterminatedString,
+    RecursiveModule,
+    AmbiguousModuleFile,
+    ModuleFileNotFound,
+    UnexpectedEndOfFile,
+    InvalidCharacter,
+    UnknownAttribute,
+    UnknownExportBuiltin,
+    UnresolvedModule,
+    GenericsInUsingPath,
+    UnreadableFile,
+    InvalidEscape,
+    InvalidCharLiteral,
+    InvalidNumberLiteral,
+    NumberOutOfRange,
+    UnknownLanguageItem,
+    DuplicateLanguageItem,
+    UnterminatedComment,
+    DuplicateComponentMember,
+    NonConstantInitializer,
+    MissingMainFunction,
+    DuplicateMainFunction,
+    InvalidMainSignature,
+    PrivateItem,
+    ChainedOperator,
+}
+
+impl ErrorKind {
+    /// Every error kind, ordered by code.
+    pub const ALL: [ErrorKind; 26] = {
+        use ErrorKind::*;
+        [
+            UnexpectedToken,
+            UnterminatedString,
+            RecursiveModule,
+            AmbiguousModuleFile,
+            ModuleFileNotFound,
+            UnexpectedEndOfFile,
+            InvalidCharacter,
+            UnknownAttribute,
+            UnknownExportBuiltin,
+            UnresolvedModule,
+            GenericsInUsingPath,
+            UnreadableFile,
+            InvalidEscape,
+            InvalidCharLiteral,
+            InvalidNumberLiteral,
+            NumberOutOfRange,
+            UnknownLanguageItem,
+            DuplicateLanguageItem,
+            UnterminatedComment,
+            DuplicateComponentMember,
+            NonConstantInitializer,
+            MissingMainFunction,
+            DuplicateMainFunction,
+            InvalidMainSignature,
+            PrivateItem,
+            ChainedOperator,
+        ]
+    };
+
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.code() == code)
+    }
+
+    /// Looks an error kind up by its code as it is shown to the user, eg. `E0001`.
+    /// The leading `E` and zeroes are optional.
+    pub fn parse_code(input: &str) -> Option<Self> {
+        let digits = input.strip_prefix(['E', 'e']).unwrap_or(input);
+        Self::from_code(digits.parse().ok()?)
+    }
+
+    /// Returns the long form markdown explanation of the error, with an example of code causing it.
+    pub fn explanation(&self) -> &'static str {
+        use ErrorKind::*;
+        match self {
+            UnexpectedToken => include_str!("E0001.md"),
+            UnterminatedString => include_str!("E0002.md"),
+            RecursiveModule => include_str!("E0003.md"),
+            AmbiguousModuleFile => include_str!("E0004.md"),
+            ModuleFileNotFound => include_str!("E0005.md"),
+            UnexpectedEndOfFile => include_str!("E0006.md"),
+            InvalidCharacter => include_str!("E0007.md"),
+            UnknownAttribute => include_str!("E0008.md"),
+            UnknownExportBuiltin => include_str!("E0009.md"),
+            UnresolvedModule => include_str!("E0010.md"),
+            GenericsInUsingPath => include_str!("E0011.md"),
+            UnreadableFile => include_str!("E0012.md"),
+            InvalidEscape => include_str!("E0013.md"),
+            InvalidCharLiteral => include_str!("E0014.md"),
+            InvalidNumberLiteral => include_str!("E0015.md"),
+            NumberOutOfRange => include_str!("E0016.md"),
+            UnknownLanguageItem => include_str!("E0017.md"),
+            DuplicateLanguageItem => include_str!("E0018.md"),
+            UnterminatedComment => include_str!("E0019.md"),
+            DuplicateComponentMember => include_str!("E0020.md"),
+            NonConstantInitializer => include_str!("E0021.md"),
+            MissingMainFunction => include_str!("E0022.md"),
+            DuplicateMainFunction => include_str!("E0023.md"),
+            InvalidMainSignature => include_str!("E0024.md"),
+            PrivateItem => include_str!("E0025.md"),
+            ChainedOperator => include_str!("E0026.md"),
+        }
+    }
+}
+
+impl Error for ErrorKind {
+    fn name(&self) -> &'static str {
+        use ErrorKind::*;
+        match self {
+            UnexpectedToken => "unexpected token",
+            UnterminatedString => "unterminated string",
+            RecursiveModule => "recursive module",
+            AmbiguousModuleFile => "ambiguous module file",
+            ModuleFileNotFound => "module file not found",
+            UnexpectedEndOfFile => "unexpected end of file",
+            InvalidCharacter => "invalid character",
+            UnknownAttribute => "unknown attribute",
+            UnknownExportBuiltin => "unknown export builtin",
+            UnresolvedModule => "unresolved module",
+            GenericsInUsingPath => "generics in using path",
+            UnreadableFile => "unreadable file",
+            InvalidEscape => "invalid escape",
+            InvalidCharLiteral => "invalid character literal",
+            InvalidNumberLiteral => "invalid number literal",
+            NumberOutOfRange => "number out of range",
+            UnknownLanguageItem => "unknown language item",
+            DuplicateLanguageItem => "duplicate language item",
+            UnterminatedComment => "unterminated comment",
+            DuplicateComponentMember => "duplicate component member",
+            NonConstantInitializer => "non constant initializer",
+            MissingMainFunction => "missing main function",
+            DuplicateMainFunction => "duplicate main function",
+            InvalidMainSignature => "invalid main signature",
+            PrivateItem => "private item",
+            ChainedOperator => "chained operator",
+        }
+    }
+
+    fn code(&self) -> u16 {
+        use ErrorKind::*;
+        match self {
+            UnexpectedToken => 1,
+            UnterminatedString => 2,
+            RecursiveModule => 3,
+            AmbiguousModuleFile => 4,
+            ModuleFileNotFound => 5,
+            UnexpectedEndOfFile => 6,
+            InvalidCharacter => 7,
+            UnknownAttribute => 8,
+            UnknownExportBuiltin => 9,
+            UnresolvedModule => 10,
+            GenericsInUsingPath => 11,
+            UnreadableFile => 12,
+            InvalidEscape => 13,
+            InvalidCharLiteral => 14,
+            InvalidNumberLiteral => 15,
+            NumberOutOfRange => 16,
+            UnknownLanguageItem => 17,
+            DuplicateLanguageItem => 18,
+            UnterminatedComment => 19,
+            DuplicateComponentMember => 20,
+            NonConstantInitializer => 21,
+            MissingMainFunction => 22,
+            DuplicateMainFunction => 23,
+            InvalidMainSignature => 24,
+            PrivateItem => 25,
+            ChainedOperator => 26,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes() {
+        for (index, kind) in ErrorKind::ALL.into_iter().enumerate() {
+            assert_eq!(kind.code() as usize, index + 1);
+            assert_eq!(ErrorKind::from_code(kind.code()), Some(kind));
+            assert!(!kind.explanation().is_empty());
+        }
+        assert_eq!(ErrorKind::from_code(0), None);
+    }
+
+    #[test]
+    fn test_parse_code() {
+        assert_eq!(
+            ErrorKind::parse_code("E0003"),
+            Some(ErrorKind::RecursiveModule)
+        );
+        assert_eq!(
+            ErrorKind::parse_code("e3"),
+            Some(ErrorKind::RecursiveModule)
+        );
+        assert_eq!(ErrorKind::parse_code("12"), Some(ErrorKind::UnreadableFile));
+        assert_eq!(ErrorKind::parse_code("E9999"), None);
+        assert_eq!(ErrorKind::parse_code("Eabc"), None);
+    }
+}
@@ -0,0 +1,146 @@
+#![allow(unused_must_use)]
+use crate::{Diagnostic, Label, Renderer};
+use pipec_file_loader::FileId;
+use pipec_span::Span;
+use std::fmt::Write;
+
+impl<'a> Renderer<'a> {
+    /// Renders the diagnostic as a single line JSON object, the format read by editors and CI tools.
+    ///
+    /// ```text
+    /// {"code":"E0001","severity":"error","message":"expected `;`, found `}`","file":"main.pipec",
+    ///  "span":{"byte_begin":46,"byte_end":47,"line_begin":3,"column_begin":1,"line_end":3,"column_end":2},
+    ///  "labels":[...],"notes":[],"help":null,"suggestions":[...]}
+    /// ```
+    pub fn render_json(&self, input: &Diagnostic) -> String {
+        let mut out = String::from("{");
+        write!(out, "\"code\":{},", optional(input.code_str().as_deref()));
+        write!(out, "\"severity\":{},", string(&input.severity.to_string()));
+        write!(out, "\"message\":{},", string(&input.message));
+        match &input.primary {
+            Some(label) => {
+                write!(out, "\"file\":{},", self.path(label.file));
+                write!(out, "\"span\":{},", self.span(label.file, label.span));
+            }
+            None => {
+                out.push_str("\"file\":null,\"span\":null,");
+            }
+        }
+
+        let labels: Vec<String> = input
+            .primary
+            .iter()
+            .map(|v| self.label(v, true))
+            .chain(input.secondary.iter().map(|v| self.label(v, false)))
+            .collect();
+        write!(out, "\"labels\":[{}],", labels.join(","));
+
+        let notes: Vec<String> = input.notes.iter().map(|v| string(v)).collect();
+        write!(out, "\"notes\":[{}],", notes.join(","));
+        write!(out, "\"help\":{},", optional(input.help.as_deref()));
+
+        let suggestions: Vec<String> = input
+            .suggestions
+            .iter()
+            .map(|v| {
+                format!(
+                    "{{\"message\":{},\"file\":{},\"span\":{},\"replacement\":{}}}",
+                    string(&v.message),
+                    self.path(v.file),
+                    self.span(v.file, v.span),
+                    string(&v.replacement)
+                )
+            })
+            .collect();
+        write!(out, "\"suggestions\":[{}]", suggestions.join(","));
+        out.push('}');
+        out
+    }
+
+    fn label(&self, input: &Label, primary: bool) -> String {
+        format!(
+            "{{\"primary\":{primary},\"message\":{},\"file\":{},\"span\":{}}}",
+            optional(input.message.as_deref()),
+            self.path(input.file),
+            self.span(input.file, input.span)
+        )
+    }
+
+    fn path(&self, file: FileId) -> String {
+        string(&self.loader.file(file).path().display().to_string())
+    }
+
+    fn span(&self, file: FileId, span: Span) -> String {
+        let (line_begin, column_begin) = self.location(file, span.begin);
+        let (line_end, column_end) = self.location(file, span.end);
+        format!(
+            "{{\"byte_begin\":{},\"byte_end\":{},\"line_begin\":{line_begin},\"column_begin\":{column_begin},\"line_end\":{line_end},\"column_end\":{column_end}}}",
+            span.begin, span.end
+        )
+    }
+}
+
+fn optional(input: Option<&str>) -> String {
+    match input {
+        Some(v) => string(v),
+        None => "null".to_string(),
+    }
+}
+
+/// Quotes and escapes a string the way JSON expects it.
+fn string(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() + 2);
+    out.push('"');
+    for v in input.chars() {
+        match v {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            v if (v as u32) < 0x20 => {
+                write!(out, "\\u{:04x}", v as u32);
+            }
+            v => out.push(v),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, Suggestion};
+    use pipec_arena::{Arena, Size};
+    use pipec_file_loader::FileLoader;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_json() {
+        let mut arena = Arena::new(Size::Kibs(4));
+        let mut loader = FileLoader::default();
+        let src = "using a\\b\n";
+        let file = loader.add_source(PathBuf::from("main.pipec"), src, &mut arena);
+        let diagnostic =
+            Diagnostic::error(ErrorKind::UnexpectedToken, "expected `;`, found \"end\"")
+                .with_primary(Label::new(file, Span { begin: 10, end: 10 }))
+                .with_suggestion(Suggestion::new(
+                    file,
+                    Span { begin: 9, end: 9 },
+                    ";",
+                    "add a `;`",
+                ));
+        let rendered = Renderer::new(&loader, &arena).render_json(&diagnostic);
+        assert_eq!(
+            rendered,
+            concat!(
+                r#"{"code":"E0001","severity":"error","message":"expected `;`, found \"end\"","#,
+                r#""file":"main.pipec","span":{"byte_begin":10,"byte_end":10,"line_begin":2,"column_begin":1,"line_end":2,"column_end":1},"#,
+                r#""labels":[{"primary":true,"message":null,"file":"main.pipec","span":{"byte_begin":10,"byte_end":10,"line_begin":2,"column_begin":1,"line_end":2,"column_end":1}}],"#,
+                r#""notes":[],"help":null,"#,
+                r#""suggestions":[{"message":"add a `;`","file":"main.pipec","span":{"byte_begin":9,"byte_end":9,"line_begin":1,"column_begin":10,"line_end":1,"column_end":10},"replacement":";"}]}"#
+            )
+        );
+    }
+}
@@ -1 +1,12 @@
+mod diagnostic;
+mod emitter;
 mod error;
+pub mod errors;
+mod json;
+mod render;
+
+pub use diagnostic::{Diagnostic, ErrorFormat, Label, Severity, Suggestion};
+pub use emitter::DiagnosticEmitter;
+pub use error::Error;
+pub use errors::ErrorKind;
+pub use render::Renderer;
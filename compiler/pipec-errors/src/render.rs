@@ -0,0 +1,242 @@
+#![allow(unused_must_use)]
+use crate::{Diagnostic, ErrorFormat, Label};
+use pipec_arena::Arena;
+use pipec_file_loader::{FileId, FileLoader};
+use std::fmt::Write;
+
+/// Turns diagnostics into the text shown to the user, annotating the source lines they point at.
+///
+/// ```text
+/// error[E0001]: expected `;`, found `}`
+///  --> main.pipec:3:1
+///   |
+/// 3 | }
+///   | ^ expected `;`
+/// ```
+pub struct Renderer<'a> {
+    pub(crate) loader: &'a FileLoader,
+    pub(crate) arena: &'a Arena,
+    format: ErrorFormat,
+}
+
+/// A label resolved to the line it starts on, with columns counted in displayed characters.
+struct LineLabel<'l> {
+    line: usize,
+    begin: usize,
+    width: usize,
+    primary: bool,
+    message: Option<&'l str>,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(loader: &'a FileLoader, arena: &'a Arena) -> Self {
+        Self {
+            loader,
+            arena,
+            format: ErrorFormat::Human,
+        }
+    }
+
+    pub fn with_format(mut self, format: ErrorFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn format(&self) -> ErrorFormat {
+        self.format
+    }
+
+    /// Renders the diagnostic in the format the renderer was created with.
+    pub fn output(&self, input: &Diagnostic) -> String {
+        match self.format {
+            ErrorFormat::Human => self.render(input),
+            ErrorFormat::Json => self.render_json(input),
+        }
+    }
+
+    pub fn render(&self, input: &Diagnostic) -> String {
+        let mut out = format!("{input}\n");
+
+        let mut labels: Vec<(&Label, bool)> = Vec::new();
+        labels.extend(input.primary.iter().map(|v| (v, true)));
+        labels.extend(input.secondary.iter().map(|v| (v, false)));
+
+        let mut files: Vec<FileId> = Vec::new();
+        for (label, _) in &labels {
+            if !files.contains(&label.file) {
+                files.push(label.file);
+            }
+        }
+
+        let gutter = labels
+            .iter()
+            .map(|(label, _)| self.location(label.file, label.span.begin).0)
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+        let pad = " ".repeat(gutter);
+
+        for (index, file) in files.iter().enumerate() {
+            let in_file: Vec<&(&Label, bool)> = labels
+                .iter()
+                .filter(|(label, _)| label.file == *file)
+                .collect();
+            let (line, column) = self.location(*file, in_file[0].0.span.begin);
+            let arrow = if index == 0 && input.primary.is_some() {
+                "-->"
+            } else {
+                ":::"
+            };
+            writeln!(
+                out,
+                "{pad}{arrow} {}:{line}:{column}",
+                self.loader.file(*file).path().display()
+            );
+            writeln!(out, "{pad} |");
+
+            let mut resolved: Vec<LineLabel> = in_file
+                .iter()
+                .map(|(label, primary)| self.resolve(label, *primary))
+                .collect();
+            resolved.sort_by_key(|v| (v.line, v.begin));
+
+            let mut last_line: Option<usize> = None;
+            for label in &resolved {
+                if last_line != Some(label.line) {
+                    if let Some(last) = last_line
+                        && label.line > last + 1
+                    {
+                        writeln!(out, "...");
+                    }
+                    writeln!(
+                        out,
+                        "{:>gutter$} | {}",
+                        label.line + 1,
+                        self.line_text(*file, label.line)
+                    );
+                    last_line = Some(label.line);
+                }
+                let marker = if label.primary { "^" } else { "-" };
+                write!(
+                    out,
+                    "{pad} | {}{}",
+                    " ".repeat(label.begin),
+                    marker.repeat(label.width)
+                );
+                match label.message {
+                    Some(message) => writeln!(out, " {message}"),
+                    None => writeln!(out),
+                };
+            }
+        }
+
+        if !files.is_empty()
+            && (!input.notes.is_empty() || input.help.is_some() || !input.suggestions.is_empty())
+        {
+            writeln!(out, "{pad} |");
+        }
+        for note in &input.notes {
+            writeln!(out, "{pad} = note: {note}");
+        }
+        if let Some(help) = &input.help {
+            writeln!(out, "{pad} = help: {help}");
+        }
+        for suggestion in &input.suggestions {
+            writeln!(out, "{pad} = help: {}", suggestion.message);
+        }
+        out
+    }
+
+    /// Returns the 1-based line and column of a byte offset.
+    pub fn location(&self, file: FileId, offset: usize) -> (usize, usize) {
+        let source = self.loader.file(file);
+        let line = source.line_index(offset);
+        let begin = source.line_range(line).start;
+        let src = self.source(file);
+        let column = src[begin..offset.min(src.len())].chars().count() + 1;
+        (line + 1, column)
+    }
+
+    fn resolve<'l>(&self, label: &'l Label, primary: bool) -> LineLabel<'l> {
+        let source = self.loader.file(label.file);
+        let src = self.source(label.file);
+        let line = source.line_index(label.span.begin);
+        let range = source.line_range(line);
+        let begin = display_width(&src[range.start..label.span.begin.min(range.end)]);
+        let end = display_width(&src[range.start..label.span.end.clamp(range.start, range.end)]);
+        LineLabel {
+            line,
+            begin,
+            width: end.saturating_sub(begin).max(1),
+            primary,
+            message: label.message.as_deref(),
+        }
+    }
+
+    fn line_text(&self, file: FileId, line: usize) -> String {
+        let range = self.loader.file(file).line_range(line);
+        self.source(file)[range]
+            .trim_end_matches('\r')
+            .replace('\t', "    ")
+    }
+
+    pub(crate) fn source(&self, file: FileId) -> &'a str {
+        self.arena.take_str_slice(self.loader.load(file))
+    }
+}
+
+/// The amount of columns a piece of a line takes up once tabs are expanded to four spaces.
+fn display_width(input: &str) -> usize {
+    input.chars().map(|v| if v == '\t' { 4 } else { 1 }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+    use pipec_arena::Size;
+    use pipec_span::Span;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render() {
+        let mut arena = Arena::new(Size::Kibs(4));
+        let mut loader = FileLoader::default();
+        let src = "function main() => nothing {\n\timmutable x = 0\n}\n";
+        let file = loader.add_source(PathBuf::from("main.pipec"), src, &mut arena);
+        let diagnostic = Diagnostic::error(ErrorKind::UnexpectedToken, "expected `;`, found `}`")
+            .with_primary(
+                Label::new(file, Span { begin: 46, end: 47 }).with_message("expected `;`"),
+            )
+            .with_label(Label::new(file, Span { begin: 30, end: 39 }).with_message("in this"))
+            .with_help("add a `;` here");
+        let rendered = Renderer::new(&loader, &arena).render(&diagnostic);
+        assert_eq!(
+            rendered,
+            "error[E0001]: expected `;`, found `}`
+ --> main.pipec:3:1
+  |
+2 |     immutable x = 0
+  |     --------- in this
+3 | }
+  | ^ expected `;`
+  |
+  = help: add a `;` here
+"
+        );
+    }
+
+    #[test]
+    fn test_location() {
+        let mut arena = Arena::new(Size::Kibs(4));
+        let mut loader = FileLoader::default();
+        let src = "módule a;\nmodule b;";
+        let file = loader.add_source(PathBuf::from("main.pipec"), src, &mut arena);
+        let renderer = Renderer::new(&loader, &arena);
+        assert_eq!(renderer.location(file, 0), (1, 1));
+        assert_eq!(renderer.location(file, 8), (1, 8));
+        assert_eq!(renderer.location(file, 11), (2, 1));
+        assert_eq!(renderer.location(file, src.len()), (2, 10));
+    }
+}
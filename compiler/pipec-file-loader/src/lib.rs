@@ -1,33 +1,100 @@
 use pipec_arena::{ASlice, AStr, Arena};
 use std::fs::File;
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 /// This struct is for loading files into the memory, ensuring every Span points to correct memory.
 pub struct FileLoader {
     store: Vec<ASlice<AStr>>,
+    files: Vec<SourceFile>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct FileId(usize);
 
+/// The path and line table of a file opened by the FileLoader, used to turn byte offsets into lines and columns.
+#[derive(Debug)]
+pub struct SourceFile {
+    path: PathBuf,
+    line_starts: Vec<usize>,
+    len: usize,
+}
+
+impl SourceFile {
+    pub(crate) fn new(path: PathBuf, src: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(src.match_indices('\n').map(|(index, _)| index + 1));
+        Self {
+            path,
+            line_starts,
+            len: src.len(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the 0-based line a byte offset is on.
+    pub fn line_index(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        }
+    }
+
+    /// Returns the byte range of a 0-based line, without its line break.
+    pub fn line_range(&self, line: usize) -> Range<usize> {
+        let begin = self.line_starts[line];
+        let end = match self.line_starts.get(line + 1) {
+            Some(next) => next - 1,
+            None => self.len,
+        };
+        begin..end
+    }
+}
+
 impl FileLoader {
     pub fn open(&mut self, input: &PathBuf, arena: &mut Arena) -> std::io::Result<FileId> {
-        let id = self.store.len();
         let file = File::open(input)?;
         let src = arena.slice_from_read(file)?;
+        Ok(self.insert(input.clone(), src, arena))
+    }
+
+    /// Loads a source that does not come from the file system, the path is only used for diagnostics.
+    pub fn add_source(&mut self, path: PathBuf, input: &str, arena: &mut Arena) -> FileId {
+        let src = arena
+            .slice_from_read(input.as_bytes())
+            .expect("reading from a str can not fail");
+        self.insert(path, src, arena)
+    }
+
+    pub(crate) fn insert(&mut self, path: PathBuf, src: ASlice<AStr>, arena: &Arena) -> FileId {
+        let id = self.store.len();
+        self.files
+            .push(SourceFile::new(path, arena.take_str_slice(src)));
         self.store.push(src);
-        Ok(FileId(id))
+        FileId(id)
     }
 
-    pub fn load(&mut self, input: FileId) -> ASlice<AStr> {
+    pub fn load(&self, input: FileId) -> ASlice<AStr> {
         self.store[input.0]
     }
+
+    pub fn file(&self, input: FileId) -> &SourceFile {
+        &self.files[input.0]
+    }
 }
 
 impl Default for FileLoader {
     fn default() -> Self {
         Self {
             store: Vec::with_capacity(100),
+            files: Vec::with_capacity(100),
         }
     }
 }
@@ -0,0 +1,57 @@
+use pipec_ast::ast::{BinaryOpType, Expression, Spanned, UnaryOpType};
+use pipec_span::Span;
+
+/// Returns the first part of the expression that could only be evaluated at runtime,
+/// or None if the whole expression can be baked into the binary.
+/// Paths are accepted here, [`GlobalSymbolTree::check_constant_paths`](crate::GlobalSymbolTree::check_constant_paths)
+/// checks that they name other statics once every module is registered.
+pub fn runtime_part(input: &Spanned<Expression>) -> Option<Span> {
+    match &input.node {
+        Expression::NumberExpression { .. }
+        | Expression::CharExpression { .. }
+        | Expression::StringExpression { .. }
+        | Expression::BoolExpression { .. }
+        | Expression::PathExpression { .. } => None,
+        // already reported while parsing
+        Expression::Error => None,
+        Expression::TupleExpression { values } | Expression::ListExpression { values } => {
+            values.iter().find_map(runtime_part)
+        }
+        Expression::BinaryOpExpression { optype, lhs, rhs } => {
+            if is_assignment(optype) {
+                return Some(input.span);
+            }
+            runtime_part(lhs).or_else(|| runtime_part(rhs))
+        }
+        Expression::UnaryExpression { op, value } => match op {
+            UnaryOpType::Negate | UnaryOpType::Not => runtime_part(value),
+            UnaryOpType::Dereference | UnaryOpType::Reference => Some(input.span),
+        },
+        Expression::IndexExpression { value, index } => {
+            runtime_part(value).or_else(|| runtime_part(index))
+        }
+        Expression::RangeExpression { start, end, .. } => {
+            runtime_part(start).or_else(|| runtime_part(end))
+        }
+        Expression::CallExpression { .. }
+        | Expression::MethodCallExpression { .. }
+        | Expression::FieldAccessExpression { .. }
+        | Expression::TildeExpression { .. }
+        | Expression::RequiredExpression { .. }
+        | Expression::SwitchExpression { .. }
+        | Expression::IfExpression { .. } => Some(input.span),
+    }
+}
+
+#[inline]
+fn is_assignment(input: &BinaryOpType) -> bool {
+    matches!(
+        input,
+        BinaryOpType::Assign
+            | BinaryOpType::AddEqual
+            | BinaryOpType::SubtractEqual
+            | BinaryOpType::MultiplyEqual
+            | BinaryOpType::DivideEqual
+            | BinaryOpType::ModEqual
+    )
+}
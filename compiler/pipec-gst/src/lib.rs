@@ -1,64 +1,192 @@
 use pipec_arena::AStr;
 use pipec_arena::{ASlice, Arena};
+use pipec_ast::ast::Attribute;
 use pipec_ast::ast::FunctionDeclarationParameters;
 use pipec_ast::ast::Path;
 use pipec_ast::ast::PathNode;
 use pipec_ast::ast::asttree::ASTTree;
-use pipec_ast::ast::{ASTNode, Block, Generics};
-use pipec_file_loader::FileLoader;
+use pipec_ast::ast::{ASTNode, Block, Docs, Expression, Generics, Spanned, SubType, Traits};
+use pipec_ast::ast::{ComponentDeclarationBlock, ComponentDeclarationBlockStatements};
+use pipec_ast::tokenizer::ident::normalize_ident;
+use pipec_ast::tokenizer::literal::{NumberSuffix, decode_string_literal};
+use pipec_errors::{Diagnostic, DiagnosticEmitter, ErrorKind, Label};
+use pipec_file_loader::{FileId, FileLoader};
+use pipec_span::Span;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+pub mod constant;
+pub mod paths;
+
 pub struct GlobalSymbolTree<'this> {
     ast: ASTTree,
     loader: &'this mut FileLoader,
     arena: &'this mut Arena,
     src: ASlice<AStr>,
-    #[allow(unused)]
+    file: FileId,
+    /// Every builtin declared so far, each may only be declared once.
     attribute_cache: HashSet<LanguageAttribute>,
+    crate_type: CrateType,
+    diagnostics: DiagnosticEmitter,
+}
+
+/// What the compiled program is, only a binary has an entry point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrateType {
+    Binary,
+    #[default]
+    Library,
 }
 
 #[derive(Default, Debug)]
 pub struct ModuleScope<'a> {
     pub symbols: HashMap<&'a str, Symbol<'a>>,
     pub submodules: HashMap<&'a str, Self>,
+    /// The `///` comments on the module declaration and the `//!` comments inside the module,
+    /// each with the file their spans point into.
+    pub docs: Vec<(FileId, Docs)>,
+    /// The `implement` blocks of the module, keyed by the names in the path of the implementor as written.
+    pub impls: HashMap<Vec<&'a str>, Vec<Implementation<'a>>>,
+    /// The `main` function the program starts at, always an [`ASTNode::MainFunction`].
+    /// Only set on the root module of a binary.
+    pub entry: Option<Spanned<ASTNode>>,
+    /// Whether the module was declared `public`, so modules outside its parent can reach it.
+    pub public: bool,
 }
 
 impl<'this> GlobalSymbolTree<'this> {
-    pub fn new(arena: &'this mut Arena, loader: &'this mut FileLoader, ast: ASTTree) -> Self {
+    pub fn new(
+        arena: &'this mut Arena,
+        loader: &'this mut FileLoader,
+        ast: ASTTree,
+        diagnostics: DiagnosticEmitter,
+    ) -> Self {
         let src = loader.load(ast.id);
+        let file = ast.id;
         let attribute_cache = HashSet::new();
         Self {
             ast,
             arena,
             loader,
             src,
+            file,
             attribute_cache,
+            crate_type: CrateType::default(),
+            diagnostics,
         }
     }
 
+    pub fn with_crate_type(mut self, crate_type: CrateType) -> Self {
+        self.crate_type = crate_type;
+        self
+    }
+
     pub fn generate<'a>(&mut self) -> ModuleScope<'a> {
         let mut out = ModuleScope::default();
+        out.docs.push((self.ast.id, self.ast.docs.clone()));
         let stream = self.ast.stream.clone();
         let mut iter = stream.iter();
         loop {
             let next = iter.next();
             match next {
-                Some(v) => match v {
+                Some(v) => match &v.node {
                     ASTNode::EOF => {
                         break;
                     }
-                    _ => self.check_node(v.clone(), &mut out),
+                    node => {
+                        if self.crate_type == CrateType::Binary {
+                            self.find_entry(v, &mut out);
+                        }
+                        self.check_node(node.clone(), false, &mut out)
+                    }
                 },
                 None => break,
             }
         }
-        println!("{:#?}", &out);
+        if self.crate_type == CrateType::Binary && out.entry.is_none() {
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::MissingMainFunction,
+                    "`main` function not found in the root module",
+                )
+                .with_help("add `function main() => nothing { }` to the root module"),
+            );
+        }
         self.import_using(&mut out);
+        self.check_paths(&out);
         out
     }
 
-    pub(crate) fn check_node(&mut self, input: ASTNode, scope: &mut ModuleScope) {
+    /// Makes a root level `function main` the entry of the program, checking that it is the only one
+    /// and that it takes no parameters and returns `nothing`.
+    pub(crate) fn find_entry(&mut self, input: &Spanned<ASTNode>, scope: &mut ModuleScope) {
+        let mut node = &input.node;
+        while let ASTNode::Public(inner) | ASTNode::Attributed(_, inner) = node {
+            node = &inner.node;
+        }
+        let ASTNode::FunctionDeclaration {
+            name,
+            generics,
+            params,
+            block,
+            out_type,
+            ..
+        } = node
+        else {
+            return;
+        };
+        if self.ident(*name, self.src) != "main" {
+            return;
+        }
+        if let Some(previous) = &scope.entry {
+            let ASTNode::MainFunction { name: first, .. } = previous.node else {
+                unreachable!("the entry is always a main function");
+            };
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::DuplicateMainFunction,
+                    "`main` function is defined more than once",
+                )
+                .with_primary(Label::new(self.file, *name).with_message("defined again here"))
+                .with_label(Label::new(self.file, first).with_message("first defined here")),
+            );
+            return;
+        }
+        if !generics.0.is_empty() || !params.0.is_empty() || !self.returns_nothing(out_type) {
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::InvalidMainSignature,
+                    "`main` function has the wrong signature",
+                )
+                .with_primary(
+                    Label::new(self.file, *name)
+                        .with_message("expected `function main() => nothing`"),
+                )
+                .with_note("`main` is called without arguments and its result is discarded"),
+            );
+        }
+        let entry = ASTNode::MainFunction {
+            name: *name,
+            block: block.clone(),
+        };
+        scope.entry = Some(Spanned::new(entry, input.span, input.file));
+    }
+
+    /// Types aren't resolved yet, so this only accepts the plain name `nothing`.
+    #[inline]
+    pub(crate) fn returns_nothing(&mut self, out_type: &Path) -> bool {
+        match out_type.0.as_slice() {
+            [PathNode::Singly { name, generics }] => {
+                generics.0.is_empty() && self.ident(*name, self.src) == "nothing"
+            }
+            _ => false,
+        }
+    }
+
+    /// Registers an item in the scope, `public` is set when the item is wrapped in `public`.
+    pub(crate) fn check_node(&mut self, input: ASTNode, public: bool, scope: &mut ModuleScope) {
         match input {
             ASTNode::FunctionDeclaration {
                 name,
@@ -66,9 +194,9 @@ impl<'this> GlobalSymbolTree<'this> {
                 block,
                 generics,
                 out_type,
+                docs,
             } => {
-                let parsed_name = name.parse_arena(self.src, self.arena);
-                println!("found function {parsed_name}");
+                let parsed_name = self.ident(name, self.src);
                 scope.symbols.insert(
                     parsed_name,
                     Symbol::Function {
@@ -76,6 +204,9 @@ impl<'this> GlobalSymbolTree<'this> {
                         params,
                         block,
                         generics,
+                        public,
+                        file: self.file,
+                        docs,
                     },
                 );
             }
@@ -83,82 +214,401 @@ impl<'this> GlobalSymbolTree<'this> {
                 name,
                 params,
                 block,
+                docs,
+            } => {
+                let parsed_name = self.ident(name, self.src);
+                scope.symbols.insert(
+                    parsed_name,
+                    Symbol::Viewport {
+                        params,
+                        block,
+                        public,
+                        file: self.file,
+                        docs,
+                    },
+                );
+            }
+            ASTNode::StaticVariableDeclaration { .. } => self.register_static(input, public, scope),
+            ASTNode::Public(node) => self.check_node(node.node, true, scope),
+            ASTNode::TypeDeclaration {
+                name,
+                generics,
+                subtype,
+                docs,
+            } => {
+                let parsed_name = self.ident(name, self.src);
+                scope.symbols.insert(
+                    parsed_name,
+                    Symbol::Type {
+                        generics,
+                        subtype,
+                        public,
+                        file: self.file,
+                        docs,
+                    },
+                );
+            }
+            ASTNode::TraitDeclaration {
+                name,
+                generics,
+                supertraits,
+                tree,
+                docs,
+            } => {
+                let parsed_name = self.ident(name, self.src);
+                let items = self.collect_items(&tree);
+                scope.symbols.insert(
+                    parsed_name,
+                    Symbol::Trait {
+                        generics,
+                        supertraits,
+                        items,
+                        public,
+                        file: self.file,
+                        docs,
+                    },
+                );
+            }
+            ASTNode::ImplementBlock {
+                generics,
+                traitpath,
+                implementor,
+                block,
             } => {
-                let parsed_name = name.parse_arena(self.src, self.arena);
-                scope
-                    .symbols
-                    .insert(parsed_name, Symbol::Viewport { params, block });
+                let Some(key) = self.path_key(&implementor) else {
+                    return;
+                };
+                let items = self.collect_items(&block);
+                scope.impls.entry(key).or_default().push(Implementation {
+                    generics,
+                    traitpath,
+                    implementor,
+                    items,
+                    file: self.file,
+                });
             }
-            ASTNode::ModStatement { name, tree } => {
-                println!("consuming mod");
+            ASTNode::ComponentDeclaration { name, block, docs } => {
+                let parsed_name = self.ident(name, self.src);
+                let component = self.register_component(parsed_name, block, public, docs);
+                scope.symbols.insert(parsed_name, component);
+            }
+            ASTNode::ModStatement { name, tree, docs } => {
                 let old = self.src;
+                let old_file = self.file;
                 self.src = self.loader.load(tree.id);
-                let mod_name = name.parse_arena(old, self.arena);
-                let mut mod_scope = ModuleScope::default();
+                self.file = tree.id;
+                let mod_name = self.ident(name, old);
+                let mut mod_scope = ModuleScope {
+                    public,
+                    ..Default::default()
+                };
+                mod_scope.docs.push((old_file, docs));
+                mod_scope.docs.push((tree.id, tree.docs.clone()));
                 let stream = tree.stream.clone();
                 let mut iter = stream.iter();
                 loop {
                     let next = iter.next();
                     match next {
-                        Some(v) => match v {
+                        Some(v) => match &v.node {
                             ASTNode::EOF => {
                                 break;
                             }
-                            _ => self.check_node(v.clone(), &mut mod_scope),
+                            node => self.check_node(node.clone(), false, &mut mod_scope),
                         },
                         None => break,
                     }
                 }
                 scope.submodules.insert(mod_name, mod_scope);
                 self.src = old;
+                self.file = old_file;
+            }
+            ASTNode::Attributed(attributes, node) => {
+                let language = attributes.iter().find_map(|v| match v {
+                    Attribute::LanguageAttribute { name, raw } => Some((*name, *raw)),
+                    _ => None,
+                });
+                // `@language("name") public type name;`
+                let (node, public) = match node.node {
+                    ASTNode::Public(inner) => (inner.node, true),
+                    node => (node, public),
+                };
+                match (language, node) {
+                    (Some((attribute, raw)), ASTNode::TypeDeclaration { name, .. }) => {
+                        self.register_builtin(attribute, raw, name, public, scope)
+                    }
+                    (_, node) => self.check_node(node, public, scope),
+                }
             }
             _ => {}
         }
     }
 
+    /// Registers a type marked with `@language("name")` as the builtin of that name.
+    #[inline]
+    pub(crate) fn register_builtin(
+        &mut self,
+        attribute: Span,
+        raw: bool,
+        name: Span,
+        public: bool,
+        scope: &mut ModuleScope,
+    ) {
+        let builtin_name = decode_string_literal(attribute.parse_arena(self.src, self.arena), raw);
+        let Some(builtin) = LanguageAttribute::from_name(&builtin_name) else {
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::UnknownLanguageItem,
+                    format!("unknown language item `{builtin_name}`"),
+                )
+                .with_primary(Label::new(self.file, attribute)),
+            );
+            return;
+        };
+        if !self.attribute_cache.insert(builtin.clone()) {
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::DuplicateLanguageItem,
+                    format!("language item `{builtin_name}` is declared more than once"),
+                )
+                .with_primary(Label::new(self.file, attribute)),
+            );
+            return;
+        }
+        let parsed_name = self.ident(name, self.src);
+        scope
+            .symbols
+            .insert(parsed_name, Symbol::Builtin { builtin, public });
+    }
+
+    #[inline]
+    pub(crate) fn register_static(
+        &mut self,
+        input: ASTNode,
+        public: bool,
+        scope: &mut ModuleScope,
+    ) {
+        let ASTNode::StaticVariableDeclaration {
+            name,
+            constant,
+            variabletype,
+            expression,
+            docs,
+        } = input
+        else {
+            unreachable!("only called with a static declaration");
+        };
+        let parsed_name = self.ident(name, self.src);
+        self.require_constant(parsed_name, &expression);
+        scope.symbols.insert(
+            parsed_name,
+            Symbol::Static {
+                constant,
+                public,
+                variabletype,
+                expression,
+                file: self.file,
+                docs,
+            },
+        );
+    }
+
+    /// Reports the initializer of `name` if it can't be evaluated at compile time.
+    #[inline]
+    pub(crate) fn require_constant(&mut self, name: &str, expression: &Spanned<Expression>) {
+        let Some(span) = constant::runtime_part(expression) else {
+            return;
+        };
+        self.diagnostics.emit(
+            Diagnostic::error(
+                ErrorKind::NonConstantInitializer,
+                format!("`{name}` must be initialized with a constant expression"),
+            )
+            .with_primary(
+                Label::new(self.file, span).with_message("this can only be evaluated at runtime"),
+            )
+            .with_help("use literals, operators and the names of other statics or constants"),
+        );
+    }
+
+    /// Registers the items of a trait or `implement` block in a scope of their own.
+    #[inline]
+    pub(crate) fn collect_items<'a>(&mut self, tree: &ASTTree) -> BTreeMap<&'a str, Symbol<'a>> {
+        let mut items = ModuleScope::default();
+        for item in &tree.stream {
+            match &item.node {
+                ASTNode::EOF => break,
+                node => self.check_node(node.clone(), false, &mut items),
+            }
+        }
+        items.symbols.into_iter().collect()
+    }
+
+    /// The names of a path without their generics, `None` for a path with a `(..)` group in it.
+    #[inline]
+    pub(crate) fn path_key<'a>(&mut self, input: &Path) -> Option<Vec<&'a str>> {
+        input
+            .0
+            .iter()
+            .map(|v| match v {
+                PathNode::Singly { name, .. } => Some(self.ident(*name, self.src)),
+                PathNode::Multi(_) => None,
+            })
+            .collect()
+    }
+
+    /// Collects the members of a component, a member name may only be declared once per component.
+    #[inline]
+    pub(crate) fn register_component<'a>(
+        &mut self,
+        component: &str,
+        block: ComponentDeclarationBlock,
+        public: bool,
+        docs: Docs,
+    ) -> Symbol<'a> {
+        let mut members = BTreeMap::new();
+        let mut declared: HashMap<&str, Span> = HashMap::new();
+        let mut constructors = Vec::new();
+        for statement in block.contents {
+            let (name, member) = match statement.node {
+                ComponentDeclarationBlockStatements::FinalVariableDeclaration {
+                    variablename,
+                    variabletype,
+                    declarationexpression,
+                } => (
+                    variablename,
+                    ComponentMember::Final {
+                        variabletype,
+                        expression: declarationexpression,
+                    },
+                ),
+                ComponentDeclarationBlockStatements::ConstVariableDeclaration {
+                    variablename,
+                    variabletype,
+                    declarationexpression,
+                } => {
+                    let parsed_name = self.ident(variablename, self.src);
+                    self.require_constant(parsed_name, &declarationexpression);
+                    (
+                        variablename,
+                        ComponentMember::Const {
+                            variabletype,
+                            expression: declarationexpression,
+                        },
+                    )
+                }
+                ComponentDeclarationBlockStatements::FunctionDeclaration {
+                    name,
+                    generics,
+                    params,
+                    out_type,
+                    block,
+                } => (
+                    name,
+                    ComponentMember::Function {
+                        out_type,
+                        params,
+                        block,
+                        generics,
+                    },
+                ),
+                ComponentDeclarationBlockStatements::PublicConstructor { params, block } => {
+                    constructors.push(ComponentConstructor { params, block });
+                    continue;
+                }
+                ComponentDeclarationBlockStatements::RenderBlockDeclaration { .. }
+                | ComponentDeclarationBlockStatements::Error => continue,
+            };
+            let parsed_name = self.ident(name, self.src);
+            if let Some(previous) = declared.insert(parsed_name, name) {
+                self.diagnostics.emit(
+                    Diagnostic::error(
+                        ErrorKind::DuplicateComponentMember,
+                        format!(
+                            "`{parsed_name}` is declared more than once in component `{component}`"
+                        ),
+                    )
+                    .with_primary(Label::new(self.file, name).with_message("declared again here"))
+                    .with_label(
+                        Label::new(self.file, previous).with_message("first declared here"),
+                    ),
+                );
+                continue;
+            }
+            members.insert(parsed_name, member);
+        }
+        Symbol::Component {
+            members,
+            constructors,
+            public,
+            file: self.file,
+            docs,
+        }
+    }
+
     pub(crate) fn import_using(&mut self, scope: &mut ModuleScope) {
         let stream = self.ast.stream.clone();
         let iter = stream.iter();
         for next in iter {
-            match next {
+            match &next.node {
                 ASTNode::EOF => break,
                 ASTNode::UsingStatement { using } => self.use_path(using, scope),
-                ASTNode::ModStatement { name, tree } => {
-                    println!("importing module appearently");
+                ASTNode::ModStatement { name, tree, .. } => {
                     let old = self.src;
+                    let old_file = self.file;
                     self.src = self.loader.load(tree.id);
-                    let mod_name = name.parse_arena(old, self.arena);
+                    self.file = tree.id;
+                    let mod_name = self.ident(*name, old);
                     let mod_scope = scope.submodules.get_mut(mod_name).unwrap();
                     let stream = tree.stream.clone();
                     for item in stream {
-                        match item {
+                        match item.node {
                             ASTNode::EOF => break,
                             ASTNode::UsingStatement { using } => self.use_path(&using, mod_scope),
                             _ => {}
                         }
                     }
                     self.src = old;
+                    self.file = old_file;
                 }
                 _ => {}
             }
         }
     }
 
+    /// Reads a name out of a source in NFC, so every spelling of a name finds the same symbol.
+    #[inline]
+    pub(crate) fn ident<'a>(&mut self, name: Span, src: ASlice<AStr>) -> &'a str {
+        let parsed = name.parse_arena(src, self.arena);
+        match normalize_ident(parsed) {
+            Cow::Borrowed(_) => parsed,
+            Cow::Owned(v) => self.arena.alloc_str(&v),
+        }
+    }
+
+    /// Imports the item at the end of the path, every module and item after the first one
+    /// is declared outside of `target` so it has to be public.
     #[inline]
     pub(crate) fn use_path(&mut self, input: &Path, target: &mut ModuleScope) {
-        println!("using {input:#?}");
         let mut iter = input.0.iter().peekable();
         let mut current: &mut ModuleScope = target;
+        let mut outside = false;
         loop {
             let next = iter.next();
             if iter.peek().is_none() {
                 match next {
                     Some(PathNode::Singly { name, generics }) => {
                         if !generics.0.is_empty() {
-                            // TODO : compiler error
-                            unreachable!();
+                            self.report_using_generics(*name);
+                            return;
+                        }
+                        let parsed_name = self.ident(*name, self.src);
+                        if outside
+                            && let Some(symbol) = current.symbols.get(parsed_name)
+                            && !symbol.is_public()
+                        {
+                            self.report_private(parsed_name, *name, self.file);
+                            return;
                         }
-                        let parsed_name = name.parse_arena(self.src, self.arena);
                         let current_ptr: *const ModuleScope = current;
                         target
                             .symbols
@@ -179,37 +629,387 @@ impl<'this> GlobalSymbolTree<'this> {
                 break;
             } else if let Some(PathNode::Singly { name, generics }) = next {
                 if !generics.0.is_empty() {
-                    // TODO : compiler error
-                    unreachable!();
+                    self.report_using_generics(*name);
+                    return;
                 }
-                let module_name = name.parse_arena(self.src, self.arena);
-                println!("{module_name} is the modules name");
-                current = current.submodules.get_mut(module_name).unwrap_or_else(|| {
-                    // TODO : compiler error
-                    unreachable!();
-                });
+                let module_name = self.ident(*name, self.src);
+                current = match current.submodules.get_mut(module_name) {
+                    Some(v) if outside && !v.public => {
+                        self.report_private_module(module_name, *name, self.file);
+                        return;
+                    }
+                    Some(v) => v,
+                    None => {
+                        self.diagnostics.emit(
+                            Diagnostic::error(
+                                ErrorKind::UnresolvedModule,
+                                format!("could not find module `{module_name}`"),
+                            )
+                            .with_primary(
+                                Label::new(self.file, *name).with_message("module not found"),
+                            ),
+                        );
+                        return;
+                    }
+                };
+                outside = true;
+            }
+        }
+    }
+
+    /// Reports the paths in expressions that reach a private item of another module.
+    pub(crate) fn check_paths(&mut self, scope: &ModuleScope) {
+        // sorted so the diagnostics come out in the same order every time
+        let mut symbols: Vec<_> = scope.symbols.iter().collect();
+        symbols.sort_unstable_by_key(|(name, _)| **name);
+        for (name, symbol) in symbols {
+            self.check_symbol_paths(symbol, scope);
+            self.check_constant_paths(name, symbol, scope);
+        }
+        for implementation in scope.impls.values().flatten() {
+            for item in implementation.items.values() {
+                self.check_symbol_paths(item, scope);
+            }
+        }
+        for submodule in scope.submodules.values() {
+            self.check_paths(submodule);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn check_symbol_paths(&mut self, symbol: &Symbol, scope: &ModuleScope) {
+        let mut found = Vec::new();
+        let file = match symbol {
+            Symbol::Function { block, file, .. } | Symbol::Viewport { block, file, .. } => {
+                paths::block_paths(block, &mut found);
+                *file
+            }
+            Symbol::Static {
+                expression, file, ..
+            } => {
+                paths::expression_paths(expression, &mut found);
+                *file
+            }
+            Symbol::Component {
+                members,
+                constructors,
+                file,
+                ..
+            } => {
+                for member in members.values() {
+                    match member {
+                        ComponentMember::Final { expression, .. } => expression
+                            .iter()
+                            .for_each(|v| paths::expression_paths(v, &mut found)),
+                        ComponentMember::Const { expression, .. } => {
+                            paths::expression_paths(expression, &mut found)
+                        }
+                        ComponentMember::Function { block, .. } => {
+                            paths::block_paths(block, &mut found)
+                        }
+                    }
+                }
+                for constructor in constructors {
+                    paths::block_paths(&constructor.block, &mut found);
+                }
+                *file
+            }
+            Symbol::Trait { items, .. } => {
+                for item in items.values() {
+                    self.check_symbol_paths(item, scope);
+                }
+                return;
             }
+            Symbol::Type { .. } | Symbol::Builtin { .. } | Symbol::Alias(_) => return,
+        };
+        let src = self.loader.load(file);
+        for path in found {
+            self.check_path(path, scope, src, file);
         }
     }
+
+    /// Reports the paths in the initializers of statics and component constants that don't name
+    /// a static or constant. Initializers already reported by [`Self::require_constant`] are skipped.
+    pub(crate) fn check_constant_paths(
+        &mut self,
+        name: &str,
+        symbol: &Symbol,
+        scope: &ModuleScope,
+    ) {
+        match symbol {
+            Symbol::Static {
+                expression, file, ..
+            } => self.check_constant_expression(name, expression, scope, *file, &BTreeMap::new()),
+            Symbol::Component { members, file, .. } => {
+                for (member_name, member) in members {
+                    if let ComponentMember::Const { expression, .. } = member {
+                        self.check_constant_expression(
+                            member_name,
+                            expression,
+                            scope,
+                            *file,
+                            members,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `members` are the members of the component the expression is written in,
+    /// its other constants can be named directly.
+    #[inline]
+    pub(crate) fn check_constant_expression(
+        &mut self,
+        name: &str,
+        expression: &Spanned<Expression>,
+        scope: &ModuleScope,
+        file: FileId,
+        members: &BTreeMap<&str, ComponentMember>,
+    ) {
+        if constant::runtime_part(expression).is_some() {
+            return;
+        }
+        let mut found = Vec::new();
+        paths::expression_paths(expression, &mut found);
+        let src = self.loader.load(file);
+        for path in found {
+            if let [PathNode::Singly { name, .. }] = path.0.as_slice()
+                && let Some(ComponentMember::Const { .. }) = members.get(self.ident(*name, src))
+            {
+                continue;
+            }
+            let message = match self.resolve_path(path, scope, src) {
+                Some(Symbol::Static { .. }) => continue,
+                Some(_) => "this is not a static or constant",
+                None => "no static or constant with this name",
+            };
+            self.diagnostics.emit(
+                Diagnostic::error(
+                    ErrorKind::NonConstantInitializer,
+                    format!("`{name}` must be initialized with a constant expression"),
+                )
+                .with_primary(Label::new(file, paths::path_span(path)).with_message(message))
+                .with_help("use literals, operators and the names of other statics or constants"),
+            );
+        }
+    }
+
+    /// Finds the symbol a path names from the module it is written in, looking through `using` imports.
+    #[inline]
+    pub(crate) fn resolve_path<'s, 'a>(
+        &mut self,
+        input: &Path,
+        scope: &'s ModuleScope<'a>,
+        src: ASlice<AStr>,
+    ) -> Option<&'s Symbol<'a>> {
+        let (last, modules) = input.0.split_last()?;
+        let mut current = scope;
+        for node in modules {
+            let PathNode::Singly { name, .. } = node else {
+                return None;
+            };
+            current = current.submodules.get(self.ident(*name, src))?;
+        }
+        let PathNode::Singly { name, .. } = last else {
+            return None;
+        };
+        let name = self.ident(*name, src);
+        let mut symbol = current.symbols.get(name)?;
+        while let Symbol::Alias(module) = symbol {
+            // SAFETY: aliases point into the tree being checked, which is not modified while paths are checked
+            symbol = unsafe { &**module }.symbols.get(name)?;
+        }
+        Some(symbol)
+    }
+
+    /// Follows a path from the module it is written in. Names that can't be found are left alone,
+    /// only the items and modules that exist are checked to be public.
+    #[inline]
+    pub(crate) fn check_path(
+        &mut self,
+        input: &Path,
+        scope: &ModuleScope,
+        src: ASlice<AStr>,
+        file: FileId,
+    ) {
+        let mut current = scope;
+        let mut iter = input.0.iter().enumerate().peekable();
+        while let Some((index, PathNode::Singly { name, .. })) = iter.next() {
+            let parsed_name = self.ident(*name, src);
+            if iter.peek().is_none() {
+                if index > 0
+                    && let Some(symbol) = current.symbols.get(parsed_name)
+                    && !symbol.is_public()
+                {
+                    self.report_private(parsed_name, *name, file);
+                }
+                return;
+            }
+            current = match current.submodules.get(parsed_name) {
+                Some(v) if index > 0 && !v.public => {
+                    self.report_private_module(parsed_name, *name, file);
+                    return;
+                }
+                Some(v) => v,
+                None => return,
+            };
+        }
+    }
+
+    #[inline]
+    pub(crate) fn report_private(&mut self, name: &str, span: Span, file: FileId) {
+        self.diagnostics.emit(
+            Diagnostic::error(ErrorKind::PrivateItem, format!("`{name}` is private"))
+                .with_primary(Label::new(file, span).with_message("private item"))
+                .with_help(format!(
+                    "declare `{name}` as `public` to use it outside of its module"
+                )),
+        );
+    }
+
+    #[inline]
+    pub(crate) fn report_private_module(&mut self, name: &str, span: Span, file: FileId) {
+        self.diagnostics.emit(
+            Diagnostic::error(
+                ErrorKind::PrivateItem,
+                format!("module `{name}` is private"),
+            )
+            .with_primary(Label::new(file, span).with_message("private module"))
+            .with_help(format!(
+                "declare the module as `public module {name}` to use it outside of its parent"
+            )),
+        );
+    }
+
+    #[inline]
+    pub(crate) fn report_using_generics(&mut self, name: Span) {
+        self.diagnostics.emit(
+            Diagnostic::error(
+                ErrorKind::GenericsInUsingPath,
+                "generics are not allowed in using statements",
+            )
+            .with_primary(Label::new(self.file, name))
+            .with_help("remove the generics from the path"),
+        );
+    }
 }
 
 #[derive(Hash, Clone, Debug)]
 pub enum Symbol<'a> {
+    /// `file` is the source every span inside the declaration points into,
+    /// so literals in its block can still be read after the GST is built.
     Function {
         out_type: Path,
         params: FunctionDeclarationParameters,
         block: Block,
         generics: Generics,
+        public: bool,
+        file: FileId,
+        docs: Docs,
     },
     Viewport {
         params: FunctionDeclarationParameters,
         block: Block,
+        public: bool,
+        file: FileId,
+        docs: Docs,
+    },
+    /// A `static`, or a `const` when `constant` is set, its expression is known to be constant.
+    Static {
+        constant: bool,
+        public: bool,
+        variabletype: Path,
+        expression: Spanned<Expression>,
+        file: FileId,
+        docs: Docs,
+    },
+    Type {
+        generics: Generics,
+        subtype: SubType,
+        public: bool,
+        file: FileId,
+        docs: Docs,
+    },
+    /// The items are the functions a trait declares, with their default bodies.
+    Trait {
+        generics: Generics,
+        supertraits: Traits,
+        items: BTreeMap<&'a str, Symbol<'a>>,
+        public: bool,
+        file: FileId,
+        docs: Docs,
+    },
+    /// Members are keyed by their name, constructors are kept in the order they are declared.
+    Component {
+        members: BTreeMap<&'a str, ComponentMember>,
+        constructors: Vec<ComponentConstructor>,
+        public: bool,
+        file: FileId,
+        docs: Docs,
     },
 
-    Builtin(LanguageAttribute),
+    /// A type declared with `@language("name")`.
+    Builtin {
+        builtin: LanguageAttribute,
+        public: bool,
+    },
     Alias(*const ModuleScope<'a>),
 }
 
+impl Symbol<'_> {
+    /// Whether the symbol can be reached from outside the module declaring it.
+    /// Names brought in by `using` are never exported.
+    pub fn is_public(&self) -> bool {
+        match self {
+            Symbol::Function { public, .. }
+            | Symbol::Viewport { public, .. }
+            | Symbol::Static { public, .. }
+            | Symbol::Type { public, .. }
+            | Symbol::Trait { public, .. }
+            | Symbol::Component { public, .. }
+            | Symbol::Builtin { public, .. } => *public,
+            Symbol::Alias(_) => false,
+        }
+    }
+}
+
+#[derive(Hash, Clone, Debug)]
+pub enum ComponentMember {
+    Final {
+        variabletype: Option<Path>,
+        expression: Option<Spanned<Expression>>,
+    },
+    Const {
+        variabletype: Option<Path>,
+        expression: Spanned<Expression>,
+    },
+    Function {
+        out_type: Path,
+        params: FunctionDeclarationParameters,
+        block: Block,
+        generics: Generics,
+    },
+}
+
+/// An `implement` block, `traitpath` is None when it isn't the implementation of a trait.
+#[derive(Hash, Clone, Debug)]
+pub struct Implementation<'a> {
+    pub generics: Generics,
+    pub traitpath: Option<Path>,
+    pub implementor: Path,
+    pub items: BTreeMap<&'a str, Symbol<'a>>,
+    pub file: FileId,
+}
+
+/// `public(params) { .. }`
+#[derive(Hash, Clone, Debug)]
+pub struct ComponentConstructor {
+    pub params: FunctionDeclarationParameters,
+    pub block: Block,
+}
+
 #[derive(Hash, Clone, PartialEq, Eq, Debug)]
 pub enum LanguageAttribute {
     Integer8,
@@ -226,4 +1026,50 @@ pub enum LanguageAttribute {
     Float64,
     FloatPort,
     Nothing,
+    Boolean,
+}
+
+impl LanguageAttribute {
+    /// Looks a builtin up by the name given to it in `@language("name")`.
+    pub fn from_name(input: &str) -> Option<Self> {
+        use LanguageAttribute::*;
+        let out = match input {
+            "integer8" => Integer8,
+            "unsigned8" => Unsigned8,
+            "float8" => Float8,
+            "integer16" => Integer16,
+            "unsigned16" => Unsigned16,
+            "float16" => Float16,
+            "integer32" => Integer32,
+            "unsigned32" => Unsigned32,
+            "float32" => Float32,
+            "integer64" => Integer64,
+            "unsigned64" => Unsigned64,
+            "float64" => Float64,
+            "floatport" => FloatPort,
+            "nothing" => Nothing,
+            "boolean" => Boolean,
+            _ => return None,
+        };
+        Some(out)
+    }
+}
+
+impl From<NumberSuffix> for LanguageAttribute {
+    fn from(input: NumberSuffix) -> Self {
+        match input {
+            NumberSuffix::Integer8 => LanguageAttribute::Integer8,
+            NumberSuffix::Unsigned8 => LanguageAttribute::Unsigned8,
+            NumberSuffix::Float8 => LanguageAttribute::Float8,
+            NumberSuffix::Integer16 => LanguageAttribute::Integer16,
+            NumberSuffix::Unsigned16 => LanguageAttribute::Unsigned16,
+            NumberSuffix::Float16 => LanguageAttribute::Float16,
+            NumberSuffix::Integer32 => LanguageAttribute::Integer32,
+            NumberSuffix::Unsigned32 => LanguageAttribute::Unsigned32,
+            NumberSuffix::Float32 => LanguageAttribute::Float32,
+            NumberSuffix::Integer64 => LanguageAttribute::Integer64,
+            NumberSuffix::Unsigned64 => LanguageAttribute::Unsigned64,
+            NumberSuffix::Float64 => LanguageAttribute::Float64,
+        }
+    }
 }
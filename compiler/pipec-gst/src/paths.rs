@@ -0,0 +1,127 @@
+use pipec_ast::ast::{
+    Block, ElseBranch, Expression, FunctionBlockStatements, Path, PathNode, Spanned,
+};
+use pipec_span::Span;
+
+/// Collects the paths used as expressions in a block, in the order they are written.
+/// Paths naming types are not collected.
+pub fn block_paths<'b>(input: &'b Block, out: &mut Vec<&'b Path>) {
+    for statement in &input.0 {
+        match &statement.node {
+            FunctionBlockStatements::MutableVariableDeclaration {
+                declarationexpression,
+                ..
+            }
+            | FunctionBlockStatements::ImmutableVariableDeclaration {
+                declarationexpression,
+                ..
+            } => {
+                if let Some(expression) = declarationexpression {
+                    expression_paths(expression, out);
+                }
+            }
+            FunctionBlockStatements::ExpressionStatement { expression, .. }
+            | FunctionBlockStatements::ExportDeclaration { expression, .. } => {
+                expression_paths(expression, out)
+            }
+            FunctionBlockStatements::RenderBlock { block }
+            | FunctionBlockStatements::LoopStatement { block } => block_paths(block, out),
+            FunctionBlockStatements::WhileStatement { condition, block } => {
+                expression_paths(condition, out);
+                block_paths(block, out);
+            }
+            FunctionBlockStatements::ForStatement {
+                iterable, block, ..
+            } => {
+                expression_paths(iterable, out);
+                block_paths(block, out);
+            }
+            FunctionBlockStatements::ReturnStatement { value } => {
+                if let Some(value) = value {
+                    expression_paths(value, out);
+                }
+            }
+            FunctionBlockStatements::BreakStatement
+            | FunctionBlockStatements::ContinueStatement => {}
+        }
+    }
+}
+
+pub fn expression_paths<'b>(input: &'b Spanned<Expression>, out: &mut Vec<&'b Path>) {
+    match &input.node {
+        Expression::PathExpression { value } => out.push(value),
+        Expression::TupleExpression { values } | Expression::ListExpression { values } => {
+            values.iter().for_each(|v| expression_paths(v, out))
+        }
+        Expression::BinaryOpExpression { lhs, rhs, .. } => {
+            expression_paths(lhs, out);
+            expression_paths(rhs, out);
+        }
+        Expression::UnaryExpression { value, .. }
+        | Expression::FieldAccessExpression { value, .. }
+        | Expression::TildeExpression { value }
+        | Expression::RequiredExpression { value } => expression_paths(value, out),
+        Expression::CallExpression {
+            function,
+            arguments,
+        } => {
+            expression_paths(function, out);
+            arguments.iter().for_each(|v| expression_paths(v, out));
+        }
+        Expression::MethodCallExpression {
+            receiver,
+            arguments,
+            ..
+        } => {
+            expression_paths(receiver, out);
+            arguments.iter().for_each(|v| expression_paths(v, out));
+        }
+        Expression::IndexExpression { value, index } => {
+            expression_paths(value, out);
+            expression_paths(index, out);
+        }
+        Expression::RangeExpression { start, end, .. } => {
+            expression_paths(start, out);
+            expression_paths(end, out);
+        }
+        Expression::SwitchExpression { predicate, block } => {
+            expression_paths(predicate, out);
+            for arm in &block.0 {
+                expression_paths(&arm.lhs, out);
+                expression_paths(&arm.rhs, out);
+            }
+        }
+        Expression::IfExpression {
+            condition,
+            block,
+            otherwise,
+        } => {
+            expression_paths(condition, out);
+            block_paths(block, out);
+            match otherwise {
+                Some(ElseBranch::If(branch)) => expression_paths(branch, out),
+                Some(ElseBranch::Block(block)) => block_paths(block, out),
+                None => {}
+            }
+        }
+        Expression::NumberExpression { .. }
+        | Expression::CharExpression { .. }
+        | Expression::StringExpression { .. }
+        | Expression::BoolExpression { .. }
+        | Expression::Error => {}
+    }
+}
+
+/// Returns the span from the first to the last name of a path.
+pub fn path_span(input: &Path) -> Span {
+    let mut names = input.0.iter().filter_map(|v| match v {
+        PathNode::Singly { name, .. } => Some(*name),
+        PathNode::Multi(_) => None,
+    });
+    let first = names.next().unwrap_or_default();
+    let last = names.next_back().unwrap_or(first);
+    Span {
+        begin: first.begin,
+        end: last.end,
+    }
+}
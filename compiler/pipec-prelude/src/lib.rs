@@ -1,35 +1,97 @@
 use pipec_arena::{Arena, Size};
-use pipec_args::{Args, Parser};
+use pipec_args::{Args, CrateType, Parser};
 use pipec_ast::{RecursiveGuard, ast::ASTGenerator, tokenizer::Tokenizer};
+use pipec_errors::{Diagnostic, DiagnosticEmitter, ErrorFormat, ErrorKind, Renderer};
 use pipec_file_loader::*;
 use pipec_gst::GlobalSymbolTree;
 
 /// This is where the compiler code begins.
 pub fn run_compiler() {
     let args = Args::parse();
-    println!("{:#?}", &args.file);
+    if let Some(code) = &args.explain {
+        explain(code);
+    }
+    let file = args
+        .file
+        .expect("clap requires a file unless --explain is given");
 
+    let diagnostics = DiagnosticEmitter::default();
+    let format = args.error_format;
     let mut arena = Arena::new(Size::Gigs(1));
     let mut loader = FileLoader::default();
-    let file_id = loader.open(&args.file, &mut arena).unwrap();
+    let file_id = match loader.open(&file, &mut arena) {
+        Ok(v) => v,
+        Err(err) => {
+            diagnostics.emit(Diagnostic::error(
+                ErrorKind::UnreadableFile,
+                format!("could not read {}: {err}", file.display()),
+            ));
+            diagnostics.flush(&Renderer::new(&loader, &arena).with_format(format));
+            std::process::exit(1);
+        }
+    };
     let file_contents_slice = loader.load(file_id);
     let file_source = arena.take_str_slice(file_contents_slice);
 
-    let mut tokentree = Tokenizer::new(file_source).tree();
+    let mut tokentree = Tokenizer::new(file_source, file_id, diagnostics.clone()).tree();
     let mut guard = RecursiveGuard::default();
 
     let ast_generator = ASTGenerator::new(
         file_id,
         &mut tokentree,
-        args.file,
+        file,
         &mut arena,
         &mut guard,
         &mut loader,
+        diagnostics.clone(),
     );
     let ast_tree = ast_generator.tree();
+    abort_if_errors(&diagnostics, &loader, &arena, format);
 
-    let mut gst = GlobalSymbolTree::new(&mut arena, &mut loader, ast_tree);
-    let scope = gst.generate();
-    println!("{:#?}", scope);
-    println!("{} bytes used for arena", &arena.index());
+    let mut gst = GlobalSymbolTree::new(&mut arena, &mut loader, ast_tree, diagnostics.clone())
+        .with_crate_type(crate_type(args.crate_type));
+    gst.generate();
+    abort_if_errors(&diagnostics, &loader, &arena, format);
+}
+
+fn crate_type(input: CrateType) -> pipec_gst::CrateType {
+    match input {
+        CrateType::Bin => pipec_gst::CrateType::Binary,
+        CrateType::Lib => pipec_gst::CrateType::Library,
+    }
+}
+
+/// Prints the explanation of an error code and exits.
+fn explain(code: &str) -> ! {
+    match ErrorKind::parse_code(code) {
+        Some(kind) => {
+            print!("{}", kind.explanation());
+            std::process::exit(0);
+        }
+        None => {
+            eprintln!("error: `{code}` is not a valid error code");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints every reported diagnostic and stops the compiler if any of them is an error.
+fn abort_if_errors(
+    diagnostics: &DiagnosticEmitter,
+    loader: &FileLoader,
+    arena: &Arena,
+    format: ErrorFormat,
+) {
+    let errors = diagnostics.error_count();
+    let code = diagnostics.diagnostics().iter().find_map(|v| v.code_str());
+    diagnostics.flush(&Renderer::new(loader, arena).with_format(format));
+    if errors > 0 {
+        if format == ErrorFormat::Human {
+            eprintln!("aborting due to {errors} previous error(s)");
+            if let Some(code) = code {
+                eprintln!("for more information about this error, try `pipecc --explain {code}`");
+            }
+        }
+        std::process::exit(1);
+    }
 }
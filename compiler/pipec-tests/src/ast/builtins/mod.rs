@@ -0,0 +1,37 @@
+use pipec_gst::{LanguageAttribute, Symbol};
+
+#[test]
+fn test_language_attributes() {
+    crate::test_file_generation!("language.pipec", scope scope);
+    assert!(matches!(
+        scope.symbols.get("bool"),
+        Some(Symbol::Builtin {
+            builtin: LanguageAttribute::Boolean,
+            ..
+        })
+    ));
+    assert!(matches!(
+        scope.symbols.get("u32"),
+        Some(Symbol::Builtin {
+            builtin: LanguageAttribute::Unsigned32,
+            ..
+        })
+    ));
+    assert!(scope.symbols.contains_key("main"));
+}
+
+#[test]
+fn test_public_language_attributes() {
+    crate::test_file_generation!("public.pipec", scope scope);
+    for (name, builtin, public) in [
+        ("bool", LanguageAttribute::Boolean, true),
+        ("f32", LanguageAttribute::Float32, true),
+        ("u32", LanguageAttribute::Unsigned32, false),
+    ] {
+        let Some(symbol @ Symbol::Builtin { builtin: found, .. }) = scope.symbols.get(name) else {
+            panic!("expected a builtin, found {:?}", scope.symbols.get(name));
+        };
+        assert_eq!(*found, builtin);
+        assert_eq!(symbol.is_public(), public, "{name}");
+    }
+}
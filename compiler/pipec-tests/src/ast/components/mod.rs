@@ -0,0 +1,105 @@
+use pipec_errors::{Error, ErrorKind};
+use pipec_gst::{ComponentMember, Symbol};
+
+#[test]
+fn test_component_members() {
+    crate::test_file_generation!("members.pipec", scope scope);
+    let src = include_str!("members.pipec");
+
+    let Some(Symbol::Component {
+        members,
+        constructors,
+        docs,
+        ..
+    }) = scope.symbols.get("Circle")
+    else {
+        panic!(
+            "expected a component, found {:?}",
+            scope.symbols.get("Circle")
+        );
+    };
+    assert_eq!(docs.text(src), "A circle drawn around a center point.");
+
+    let names: Vec<&str> = members.keys().copied().collect();
+    assert_eq!(names, ["SIDES", "area", "radius", "scaled", "segments"]);
+    assert!(matches!(
+        members["radius"],
+        ComponentMember::Final {
+            expression: Some(_),
+            ..
+        }
+    ));
+    assert!(matches!(
+        members["segments"],
+        ComponentMember::Final {
+            variabletype: Some(_),
+            expression: None,
+        }
+    ));
+    assert!(matches!(members["SIDES"], ComponentMember::Const { .. }));
+    let ComponentMember::Function {
+        params, generics, ..
+    } = &members["scaled"]
+    else {
+        panic!("expected a function, found {:?}", members["scaled"]);
+    };
+    assert_eq!(params.0.len(), 2);
+    assert_eq!(generics.0.len(), 1);
+
+    assert_eq!(constructors.len(), 2);
+    assert_eq!(constructors[0].params.0.len(), 1);
+    assert!(constructors[1].params.0.is_empty());
+}
+
+#[test]
+fn test_duplicate_members() {
+    crate::test_file_generation!("duplicates.pipec", scope scope, diagnostics diagnostics);
+    let diagnostics = diagnostics.diagnostics();
+    assert_eq!(diagnostics.len(), 2);
+    for diagnostic in &diagnostics {
+        assert_eq!(
+            diagnostic.code,
+            Some(ErrorKind::DuplicateComponentMember.code())
+        );
+        assert_eq!(
+            diagnostic.message,
+            "`radius` is declared more than once in component `Circle`"
+        );
+    }
+
+    let Some(Symbol::Component { members, .. }) = scope.symbols.get("Circle") else {
+        panic!(
+            "expected a component, found {:?}",
+            scope.symbols.get("Circle")
+        );
+    };
+    assert!(matches!(members["radius"], ComponentMember::Final { .. }));
+}
+
+#[test]
+fn test_member_recovery() {
+    crate::test_file_generation!("recovery.pipec", scope scope, diagnostics diagnostics);
+    let messages: Vec<String> = diagnostics
+        .diagnostics()
+        .into_iter()
+        .map(|v| v.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "expected `:` or `=`, found identifier",
+            "expected an expression, found `;`",
+            "expected an identifier, found `=>`",
+        ]
+    );
+
+    let Some(Symbol::Component { members, .. }) = scope.symbols.get("Circle") else {
+        panic!(
+            "expected a component, found {:?}",
+            scope.symbols.get("Circle")
+        );
+    };
+    let names: Vec<&str> = members.keys().copied().collect();
+    assert_eq!(names, ["area", "segments"]);
+    assert!(scope.symbols.contains_key("after"));
+}
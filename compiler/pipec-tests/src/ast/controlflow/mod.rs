@@ -0,0 +1,103 @@
+use pipec_ast::ast::{ASTNode, ElseBranch, Expression, FunctionBlockStatements, Spanned};
+
+fn body(node: &ASTNode) -> Vec<&FunctionBlockStatements> {
+    match node {
+        ASTNode::FunctionDeclaration { block, .. } => block.0.iter().map(|v| &v.node).collect(),
+        v => panic!("expected a function, found {v:?}"),
+    }
+}
+
+#[test]
+fn test_control_flow() {
+    crate::test_file_generation!("test.pipec", ast ast_tree);
+
+    let clamp = body(&ast_tree.stream[0]);
+    let [
+        FunctionBlockStatements::ExpressionStatement {
+            expression:
+                Spanned {
+                    node:
+                        Expression::IfExpression {
+                            otherwise: Some(ElseBranch::If(otherwise)),
+                            ..
+                        },
+                    ..
+                },
+            hidden: false,
+        },
+        FunctionBlockStatements::ExpressionStatement {
+            expression:
+                Spanned {
+                    node: Expression::PathExpression { .. },
+                    ..
+                },
+            hidden: false,
+        },
+    ] = clamp.as_slice()
+    else {
+        panic!("unexpected body {clamp:?}");
+    };
+    assert!(matches!(
+        otherwise.node,
+        Expression::IfExpression {
+            otherwise: None,
+            ..
+        }
+    ));
+
+    let count = body(&ast_tree.stream[1]);
+    assert!(matches!(
+        count.as_slice(),
+        [
+            FunctionBlockStatements::MutableVariableDeclaration { .. },
+            FunctionBlockStatements::WhileStatement { .. },
+            FunctionBlockStatements::LoopStatement { .. },
+            FunctionBlockStatements::ImmutableVariableDeclaration {
+                declarationexpression: Some(Spanned {
+                    node: Expression::IfExpression {
+                        otherwise: Some(ElseBranch::Block(_)),
+                        ..
+                    },
+                    ..
+                }),
+                ..
+            },
+            FunctionBlockStatements::ReturnStatement { value: None },
+        ]
+    ));
+
+    let iterables: Vec<&Expression> = body(&ast_tree.stream[2])
+        .into_iter()
+        .filter_map(|v| match v {
+            FunctionBlockStatements::ForStatement { iterable, .. } => Some(&iterable.node),
+            _ => None,
+        })
+        .collect();
+    assert!(matches!(
+        iterables.as_slice(),
+        [
+            Expression::PathExpression { .. },
+            Expression::RangeExpression {
+                inclusive: false,
+                ..
+            },
+            Expression::RangeExpression {
+                inclusive: true,
+                ..
+            },
+            Expression::ListExpression { .. },
+        ]
+    ));
+    let sum = body(&ast_tree.stream[2]);
+    assert!(matches!(
+        sum.as_slice(),
+        [
+            FunctionBlockStatements::MutableVariableDeclaration { .. },
+            FunctionBlockStatements::ForStatement { .. },
+            FunctionBlockStatements::ForStatement { .. },
+            FunctionBlockStatements::ForStatement { .. },
+            FunctionBlockStatements::ForStatement { .. },
+            FunctionBlockStatements::ExpressionStatement { hidden: false, .. },
+        ]
+    ));
+}
@@ -0,0 +1,98 @@
+use pipec_ast::tokenizer::{Token, Tokenizer};
+use pipec_errors::{DiagnosticEmitter, Error, ErrorKind};
+use pipec_file_loader::FileLoader;
+use std::path::PathBuf;
+
+#[test]
+fn test_diagnostics() {
+    {
+        crate::test_file_generation!("missingsemicolon.pipec",diagnostics diagnostics);
+        let reported = diagnostics.diagnostics();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].code, Some(ErrorKind::UnexpectedToken.code()));
+        assert_eq!(reported[0].message, "expected `;`, found `}`");
+    }
+    {
+        crate::test_file_generation!("unknownattribute.pipec",diagnostics diagnostics);
+        let reported = diagnostics.diagnostics();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].code, Some(ErrorKind::UnknownAttribute.code()));
+    }
+    {
+        crate::test_file_generation!("invalidcharacter.pipec",diagnostics diagnostics);
+        let reported = diagnostics.diagnostics();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].code, Some(ErrorKind::InvalidCharacter.code()));
+        let span = reported[0].primary.as_ref().unwrap().span;
+        assert_eq!(span.end - span.begin, 1);
+    }
+}
+
+#[test]
+fn test_lexer_diagnostics() {
+    {
+        crate::test_file_generation!("nestedcomments.pipec");
+    }
+    {
+        crate::test_file_generation!("unterminatedcomment.pipec",diagnostics diagnostics);
+        let reported = diagnostics.diagnostics();
+        let messages: Vec<&str> = reported.iter().map(|v| v.message.as_str()).collect();
+        // lexing goes on after every invalid character
+        assert_eq!(
+            messages,
+            [
+                "invalid character `$` in source",
+                "invalid character `¤` in source",
+                "invalid character `§` in source",
+                "unterminated block comment",
+            ]
+        );
+        let comment = &reported[3];
+        assert_eq!(comment.code, Some(ErrorKind::UnterminatedComment.code()));
+        let src = include_str!("unterminatedcomment.pipec");
+        let primary = comment.primary.as_ref().unwrap().span;
+        assert_eq!(primary.begin, src.find("/* outer").unwrap());
+        assert_eq!(comment.secondary.len(), 1);
+        assert_eq!(
+            comment.secondary[0].span.begin,
+            src.find("/* never").unwrap()
+        );
+    }
+    {
+        crate::test_file_generation!("unterminatedstring.pipec",diagnostics diagnostics);
+        let reported = diagnostics.diagnostics();
+        let messages: Vec<&str> = reported.iter().map(|v| v.message.as_str()).collect();
+        // the string runs to the end of the file, the parser only notices what is missing after it
+        assert_eq!(
+            messages,
+            [
+                "unterminated string literal",
+                "expected `;`, found end of file"
+            ]
+        );
+        assert_eq!(reported[0].code, Some(ErrorKind::UnterminatedString.code()));
+    }
+}
+
+#[test]
+fn test_invalid_character_run() {
+    // a blob of bytes that aren't source, long enough to overflow the stack if every character cost a frame
+    let src = format!("before {} after", "$".repeat(100_000));
+    let mut arena = pipec_arena::Arena::new(pipec_arena::Size::Kibs(256));
+    let file = FileLoader::default().add_source(PathBuf::from("blob.pipec"), &src, &mut arena);
+    let diagnostics = DiagnosticEmitter::default();
+    let names: Vec<&str> = Tokenizer::new(&src, file, diagnostics.clone())
+        .filter_map(|(token, _)| match token {
+            Token::Ident(name) => Some(name.parse_str(&src)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["before", "after"]);
+    let reported = diagnostics.diagnostics();
+    assert_eq!(reported.len(), 100_000);
+    assert!(
+        reported
+            .iter()
+            .all(|v| v.code == Some(ErrorKind::InvalidCharacter.code()))
+    );
+}
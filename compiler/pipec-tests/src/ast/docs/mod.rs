@@ -0,0 +1,60 @@
+use pipec_errors::Severity;
+use pipec_gst::Symbol;
+
+#[test]
+fn test_documented_items() {
+    crate::test_file_generation!("documented.pipec", scope scope);
+    let src = include_str!("documented.pipec");
+
+    let [(_, root)] = scope.docs.as_slice() else {
+        panic!("unexpected module docs {:?}", scope.docs);
+    };
+    assert_eq!(
+        root.text(src),
+        "Geometry helpers.\nEverything here works on `f32`."
+    );
+
+    let Some(Symbol::Function { docs, .. }) = scope.symbols.get("area") else {
+        panic!("expected a function, found {:?}", scope.symbols.get("area"));
+    };
+    assert_eq!(
+        docs.text(src),
+        "Returns the area of a rectangle.\n\nBoth sides are expected to be positive."
+    );
+    let Some(Symbol::Function { docs, .. }) = scope.symbols.get("undocumented") else {
+        panic!(
+            "expected a function, found {:?}",
+            scope.symbols.get("undocumented")
+        );
+    };
+    assert!(docs.is_empty());
+    let Some(Symbol::Viewport { docs, .. }) = scope.symbols.get("main") else {
+        panic!("expected a viewport, found {:?}", scope.symbols.get("main"));
+    };
+    assert_eq!(docs.text(src), "The main view.");
+
+    let shapes = &scope.submodules["shapes"];
+    let docs: Vec<String> = shapes.docs.iter().map(|(_, v)| v.text(src)).collect();
+    assert_eq!(
+        docs,
+        ["Shapes that can be drawn.", "Everything that can be drawn."]
+    );
+    let Some(Symbol::Function { docs, .. }) = shapes.symbols.get("point") else {
+        panic!(
+            "expected a function, found {:?}",
+            shapes.symbols.get("point")
+        );
+    };
+    assert_eq!(docs.text(src), "A single point.");
+}
+
+#[test]
+fn test_unused_docs() {
+    crate::test_file_generation!("unused.pipec", diagnostics diagnostics);
+    let diagnostics = diagnostics.diagnostics();
+    assert_eq!(diagnostics.len(), 3);
+    for diagnostic in diagnostics {
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.message, "unused doc comment");
+    }
+}
@@ -0,0 +1,63 @@
+use pipec_ast::ast::ASTNode;
+use pipec_errors::{Error, ErrorKind};
+
+#[test]
+fn test_entry() {
+    crate::test_file_generation!("valid.pipec", binary scope, diagnostics diagnostics);
+    assert!(diagnostics.is_empty(), "{:#?}", diagnostics.diagnostics());
+    let src = include_str!("valid.pipec");
+    let Some(entry) = &scope.entry else {
+        panic!("expected an entry");
+    };
+    let ASTNode::MainFunction { block, .. } = &entry.node else {
+        panic!("expected a main function, found {:?}", entry.node);
+    };
+    assert_eq!(block.0.len(), 1);
+    assert!(
+        entry
+            .span
+            .parse_str(src)
+            .starts_with("public function main()")
+    );
+    assert!(scope.submodules["screens"].entry.is_none());
+}
+
+#[test]
+fn test_library_has_no_entry() {
+    crate::test_file_generation!("missing.pipec", scope scope);
+    assert!(scope.entry.is_none());
+}
+
+#[test]
+fn test_entry_diagnostics() {
+    fn codes(diagnostics: &pipec_errors::DiagnosticEmitter) -> Vec<u16> {
+        diagnostics
+            .diagnostics()
+            .iter()
+            .filter_map(|v| v.code)
+            .collect()
+    }
+    {
+        crate::test_file_generation!("missing.pipec", binary scope, diagnostics diagnostics);
+        assert!(scope.entry.is_none());
+        assert_eq!(codes(&diagnostics), [ErrorKind::MissingMainFunction.code()]);
+    }
+    {
+        crate::test_file_generation!("duplicate.pipec", binary scope, diagnostics diagnostics);
+        assert!(scope.entry.is_some());
+        assert_eq!(
+            codes(&diagnostics),
+            [ErrorKind::DuplicateMainFunction.code()]
+        );
+        let reported = diagnostics.diagnostics();
+        assert_eq!(reported[0].secondary.len(), 1);
+    }
+    {
+        crate::test_file_generation!("signature.pipec", binary scope, diagnostics diagnostics);
+        assert!(scope.entry.is_some());
+        assert_eq!(
+            codes(&diagnostics),
+            [ErrorKind::InvalidMainSignature.code()]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use pipec_ast::ast::asttree::ASTTree;
+use pipec_ast::ast::{
+    ASTNode, BinaryOpType, Expression, FunctionBlockStatements, Generics, Path, PathNode, Spanned,
+    UnaryOpType,
+};
+use pipec_ast::tokenizer::literal::decode_string_literal;
+use pipec_errors::{Error, ErrorKind};
+use pipec_gst::Symbol;
+
+/// Writes an expression out as an s-expression so its shape can be compared against a string.
+fn sexpr(input: &Expression, src: &str) -> String {
+    match input {
+        Expression::NumberExpression { value, .. } => value.parse_str(src).to_string(),
+        Expression::BoolExpression { value } => value.to_string(),
+        Expression::StringExpression { value, raw: false } => {
+            format!("\"{}\"", value.parse_str(src))
+        }
+        Expression::StringExpression { value, raw: true } => {
+            format!("r\"{}\"", value.parse_str(src))
+        }
+        Expression::PathExpression { value } => path(value, src),
+        Expression::TupleExpression { values } => {
+            let values: Vec<String> = values.iter().map(|v| sexpr(v, src)).collect();
+            format!("({})", values.join(" "))
+        }
+        Expression::BinaryOpExpression { optype, lhs, rhs } => {
+            let op = match optype {
+                BinaryOpType::Add => "+",
+                BinaryOpType::Subtract => "-",
+                BinaryOpType::Multiply => "*",
+                BinaryOpType::Divide => "/",
+                BinaryOpType::Mod => "%",
+                BinaryOpType::AddEqual => "+=",
+                BinaryOpType::SubtractEqual => "-=",
+                BinaryOpType::MultiplyEqual => "*=",
+                BinaryOpType::DivideEqual => "/=",
+                BinaryOpType::ModEqual => "%=",
+                BinaryOpType::Assign => "=",
+                BinaryOpType::Equal => "==",
+                BinaryOpType::NotEqual => "!=",
+                BinaryOpType::LessThan => "<",
+                BinaryOpType::LessThanOrEqual => "<=",
+                BinaryOpType::GreaterThan => ">",
+                BinaryOpType::GreaterThanOrEqual => ">=",
+                BinaryOpType::And => "&&",
+                BinaryOpType::Or => "||",
+                BinaryOpType::BitAnd => "&",
+                BinaryOpType::BitOr => "|",
+                BinaryOpType::BitXor => "^",
+            };
+            format!("({op} {} {})", sexpr(lhs, src), sexpr(rhs, src))
+        }
+        Expression::UnaryExpression { op, value } => {
+            let op = match op {
+                UnaryOpType::Negate => "neg",
+                UnaryOpType::Not => "not",
+                UnaryOpType::Dereference => "deref",
+                UnaryOpType::Reference => "ref",
+            };
+            format!("({op} {})", sexpr(value, src))
+        }
+        Expression::TildeExpression { value } => format!("(~ {})", sexpr(value, src)),
+        Expression::CallExpression {
+            function,
+            arguments,
+        } => format!("(call {}{})", sexpr(function, src), list(arguments, src)),
+        Expression::MethodCallExpression {
+            receiver,
+            method,
+            generics,
+            arguments,
+        } => format!(
+            "(method {} {}{}{})",
+            sexpr(receiver, src),
+            method.parse_str(src),
+            self::generics(generics, src),
+            list(arguments, src)
+        ),
+        Expression::FieldAccessExpression { value, field } => {
+            format!("(. {} {})", sexpr(value, src), field.parse_str(src))
+        }
+        Expression::RangeExpression {
+            start,
+            end,
+            inclusive,
+        } => {
+            let op = if *inclusive { "..=" } else { ".." };
+            format!("({op} {} {})", sexpr(start, src), sexpr(end, src))
+        }
+        Expression::IndexExpression { value, index } => {
+            format!("(index {} {})", sexpr(value, src), sexpr(index, src))
+        }
+        v => panic!("unexpected expression {v:?}"),
+    }
+}
+
+/// Returns the expression of every statement in the body of the first function of a file.
+fn expressions(ast_tree: &ASTTree, src: &str) -> Vec<String> {
+    let Some(ASTNode::FunctionDeclaration { block, .. }) = ast_tree.stream.first().map(|v| &v.node)
+    else {
+        panic!("expected a function, found {:?}", ast_tree.stream);
+    };
+    block
+        .0
+        .iter()
+        .map(|v| match &v.node {
+            FunctionBlockStatements::ImmutableVariableDeclaration {
+                declarationexpression: Some(expression),
+                ..
+            }
+            | FunctionBlockStatements::MutableVariableDeclaration {
+                declarationexpression: Some(expression),
+                ..
+            }
+            | FunctionBlockStatements::ExpressionStatement { expression, .. } => {
+                sexpr(expression, src)
+            }
+            v => panic!("unexpected statement {v:?}"),
+        })
+        .collect()
+}
+
+fn path(input: &Path, src: &str) -> String {
+    let nodes: Vec<String> = input
+        .0
+        .iter()
+        .map(|v| match v {
+            PathNode::Singly { name, generics } => {
+                format!("{}{}", name.parse_str(src), self::generics(generics, src))
+            }
+            v => panic!("unexpected path node {v:?}"),
+        })
+        .collect();
+    nodes.join("\\")
+}
+
+fn generics(input: &Generics, src: &str) -> String {
+    if input.0.is_empty() {
+        return String::new();
+    }
+    let names: Vec<&str> = input.0.iter().map(|v| v.name.parse_str(src)).collect();
+    format!("[{}]", names.join(","))
+}
+
+fn list(input: &[Spanned<Expression>], src: &str) -> String {
+    input
+        .iter()
+        .map(|v| format!(" {}", sexpr(v, src)))
+        .collect()
+}
+
+#[test]
+fn test_precedence() {
+    crate::test_file_generation!("precedence.pipec", ast ast_tree);
+    let expressions = expressions(&ast_tree, include_str!("precedence.pipec"));
+    assert_eq!(
+        expressions,
+        [
+            "(- (- x y) z)",
+            "(+ (* x y) z)",
+            "(+ x (* y z))",
+            "(- (* (/ x y) z) w)",
+            "(= x (+= y z))",
+            "(* ((+ x y)) z)",
+            "0",
+            "(%= g (- x y))",
+            "(+ (% x y) z)",
+            "(|| (&& (== x y) (!= y z)) (< z w))",
+            "(&& (<= x y) (>= ((> y z)) w))",
+            "(| (^ x (& y z)) w)",
+            "(== (& x 1) 0)",
+            "(= g (|| x (< y (+ z 1))))",
+            "(.. 0 n)",
+            "(..= 0 (- n 1))",
+            "(.. (* x 2) (call len verts))",
+            "(= g (.. 1.5 2))",
+        ]
+    );
+}
+
+#[test]
+fn test_unary() {
+    crate::test_file_generation!("unary.pipec", ast ast_tree);
+    let expressions = expressions(&ast_tree, include_str!("unary.pipec"));
+    assert_eq!(
+        expressions,
+        [
+            "(neg 1.0)",
+            "(* (neg x) y)",
+            "(- x (neg 1))",
+            "(neg (neg x))",
+            "(|| (not visible) (not ((== x y))))",
+            "(= (deref p) (ref x))",
+            "(ref (deref p))",
+            "(~ (+ x y))",
+            "(ref (ref x))",
+            "(&& x (ref (ref y)))",
+        ]
+    );
+}
+
+#[test]
+fn test_postfix() {
+    crate::test_file_generation!("postfix.pipec", ast ast_tree);
+    let expressions = expressions(&ast_tree, include_str!("postfix.pipec"));
+    assert_eq!(
+        expressions,
+        [
+            "(call math\\add 1 2)",
+            "(. color r)",
+            "(index verts 3)",
+            "(index verts i)",
+            "(call make[u32])",
+            "(call make[u32,f32] x)",
+            "(call Vec[u32]\\new)",
+            "(method (index verts 0) normalize)",
+            "(method x into[f32])",
+            "(index (. mesh verts) i)",
+            "(index (index grid x) y)",
+            "(call (index handlers 0) event)",
+            "(+ (neg (. a b)) (* (call f) 2))",
+            "(. (method (call get) inner) value)",
+        ]
+    );
+}
+
+#[test]
+fn test_index_arguments() {
+    crate::test_file_generation!("index.pipec", diagnostics diagnostics);
+    let messages: Vec<String> = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|v| v.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "expected `]`, found `,`",
+            "expected an expression, found `]`"
+        ]
+    );
+}
+
+#[test]
+fn test_chained_comparisons() {
+    crate::test_file_generation!("comparisons.pipec", diagnostics diagnostics);
+    let diagnostics = diagnostics.diagnostics();
+    for diagnostic in &diagnostics {
+        assert_eq!(diagnostic.code, Some(ErrorKind::ChainedOperator.code()));
+    }
+    let messages: Vec<&str> = diagnostics.iter().map(|v| v.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "comparison operators cannot be chained",
+            "comparison operators cannot be chained",
+            "range operators cannot be chained",
+            "range operators cannot be chained",
+        ]
+    );
+}
+
+#[test]
+fn test_booleans() {
+    crate::test_file_generation!("booleans.pipec", ast ast_tree);
+    let expressions = expressions(&ast_tree, include_str!("booleans.pipec"));
+    assert_eq!(
+        expressions,
+        [
+            "true",
+            "false",
+            "(&& true (not false))",
+            "(|| (== x true) done)",
+        ]
+    );
+}
+
+#[test]
+fn test_strings() {
+    crate::test_file_generation!("strings.pipec", ast ast_tree);
+    let expressions = expressions(&ast_tree, include_str!("strings.pipec"));
+    assert_eq!(
+        expressions,
+        [
+            "\"Pipe\"",
+            "\"\"",
+            "(call window\\open \"a \\\"title\\\"\" 800)",
+            "(method \"assets/mesh.obj\" len)",
+            "r\"C:\\assets\"",
+            "(call window\\open r\"a \"title\"\" 800)",
+        ]
+    );
+}
+
+#[test]
+fn test_strings_in_symbols() {
+    crate::test_file_generation!("strings.pipec", scope scope);
+    let Some(Symbol::Function { block, .. }) = scope.symbols.get("main") else {
+        panic!("expected a function, found {:?}", scope.symbols.get("main"));
+    };
+    let src = include_str!("strings.pipec");
+    for (index, expected) in [(2, false), (5, true)] {
+        let Some(FunctionBlockStatements::ExpressionStatement { expression, .. }) =
+            block.0.get(index).map(|v| &v.node)
+        else {
+            panic!("expected a statement, found {:?}", block.0.get(index));
+        };
+        let Expression::CallExpression { arguments, .. } = &expression.node else {
+            panic!("expected a call, found {expression:?}");
+        };
+        let Some(Expression::StringExpression { value, raw }) = arguments.first().map(|v| &v.node)
+        else {
+            panic!("expected a string, found {:?}", arguments.first());
+        };
+        assert_eq!(*raw, expected);
+        assert_eq!(
+            decode_string_literal(value.parse_str(src), *raw),
+            "a \"title\""
+        );
+    }
+}
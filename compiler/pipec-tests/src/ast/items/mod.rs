@@ -0,0 +1,68 @@
+use pipec_ast::ast::SubType;
+use pipec_gst::Symbol;
+
+#[test]
+fn test_items() {
+    crate::test_file_generation!("items.pipec", scope scope);
+    let src = include_str!("items.pipec");
+
+    let Some(Symbol::Type { subtype, docs, .. }) = scope.symbols.get("Point") else {
+        panic!("expected a type, found {:?}", scope.symbols.get("Point"));
+    };
+    assert_eq!(docs.text(src), "A point on the screen.");
+    let SubType::Map(fields) = subtype else {
+        panic!("expected a map, found {subtype:?}");
+    };
+    assert_eq!(fields.keys().collect::<Vec<_>>(), ["x", "y"]);
+    assert!(matches!(
+        scope.symbols.get("Shape"),
+        Some(Symbol::Type {
+            subtype: SubType::Union(_),
+            ..
+        })
+    ));
+
+    let Some(Symbol::Trait {
+        supertraits,
+        items,
+        docs,
+        ..
+    }) = scope.symbols.get("Drawable")
+    else {
+        panic!(
+            "expected a trait, found {:?}",
+            scope.symbols.get("Drawable")
+        );
+    };
+    assert_eq!(docs.text(src), "Anything that can be drawn.");
+    assert_eq!(supertraits.0.len(), 1);
+    assert_eq!(
+        items.keys().copied().collect::<Vec<_>>(),
+        ["bounds", "draw"]
+    );
+
+    assert!(matches!(
+        scope.symbols.get("Circle"),
+        Some(Symbol::Component { .. })
+    ));
+    assert!(matches!(
+        scope.symbols.get("area"),
+        Some(Symbol::Function { .. })
+    ));
+
+    let circle = &scope.impls[&vec!["Circle"]];
+    assert_eq!(circle.len(), 2);
+    assert!(circle[0].traitpath.is_none());
+    assert!(circle[0].items.contains_key("diameter"));
+    assert!(circle[1].traitpath.is_some());
+    assert!(circle[1].items.contains_key("draw"));
+    let list = &scope.impls[&vec!["List"]];
+    assert_eq!(list[0].generics.0.len(), 1);
+
+    let shapes = &scope.submodules["shapes"];
+    assert!(matches!(
+        shapes.symbols.get("Square"),
+        Some(Symbol::Type { .. })
+    ));
+    assert!(shapes.impls[&vec!["Square"]][0].items.contains_key("area"));
+}
@@ -0,0 +1,113 @@
+use pipec_arena::{Arena, Size};
+use pipec_ast::ast::{Expression, FunctionBlockStatements};
+use pipec_ast::tokenizer::literal::{decode_char, decode_string, decode_string_literal};
+use pipec_ast::tokenizer::{Token, Tokenizer};
+use pipec_errors::DiagnosticEmitter;
+use pipec_file_loader::FileLoader;
+use pipec_gst::{LanguageAttribute, Symbol};
+use std::path::PathBuf;
+
+#[test]
+fn test_valid_literals() {
+    crate::test_file_generation!("valid.pipec");
+}
+
+#[test]
+fn test_invalid_literals() {
+    crate::test_file_generation!("invalid.pipec", diagnostics diagnostics);
+    let messages: Vec<String> = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|v| v.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "unknown character escape `\\q`",
+            "empty character literal",
+            "character literal may only contain one character",
+            "`D800` is not a valid unicode character",
+            "unterminated character literal",
+            // the unterminated literal swallowed the rest of its line, `;` included
+            "expected `;`, found keyword `immutable`",
+        ]
+    );
+}
+
+#[test]
+fn test_literal_tokens() {
+    let src = r#####"r"C:\path" r#"say "hi""# r####"a "## b"#### "a\"b" '\'' rest"#####;
+    let mut arena = Arena::new(Size::Kibs(4));
+    let file = FileLoader::default().add_source(PathBuf::from("main.pipec"), src, &mut arena);
+    let diagnostics = DiagnosticEmitter::default();
+    let tokens: Vec<Token> = Tokenizer::new(src, file, diagnostics.clone())
+        .map(|(token, _)| token)
+        .collect();
+    let [
+        Token::RawString(raw),
+        Token::RawString(hashed),
+        Token::RawString(many),
+        Token::String(string),
+        Token::Char(char),
+        Token::Ident(ident),
+    ] = tokens.as_slice()
+    else {
+        panic!("unexpected tokens {tokens:?}");
+    };
+    assert_eq!(raw.parse_str(src), r"C:\path");
+    assert_eq!(hashed.parse_str(src), r#"say "hi""#);
+    assert_eq!(many.parse_str(src), r###"a "## b"###);
+    assert_eq!(decode_string(string.parse_str(src)), "a\"b");
+    assert_eq!(decode_char(char.parse_str(src)), Some('\''));
+    assert_eq!(ident.parse_str(src), "rest");
+    assert!(diagnostics.is_empty(), "{:#?}", diagnostics.diagnostics());
+}
+
+#[test]
+fn test_raw_strings() {
+    crate::test_file_generation!("raw.pipec", scope scope);
+    let src = include_str!("raw.pipec");
+    assert!(matches!(
+        scope.symbols.get("bool"),
+        Some(Symbol::Builtin {
+            builtin: LanguageAttribute::Boolean,
+            ..
+        })
+    ));
+    assert!(matches!(
+        scope.symbols.get("u32"),
+        Some(Symbol::Builtin {
+            builtin: LanguageAttribute::Unsigned32,
+            ..
+        })
+    ));
+
+    let Some(Symbol::Function { block, .. }) = scope.symbols.get("main") else {
+        panic!("expected a function, found {:?}", scope.symbols.get("main"));
+    };
+    let values: Vec<(String, bool)> = block
+        .0
+        .iter()
+        .map(|v| match &v.node {
+            FunctionBlockStatements::ImmutableVariableDeclaration {
+                declarationexpression: Some(expression),
+                ..
+            } => match expression.node {
+                Expression::StringExpression { value, raw } => (
+                    decode_string_literal(value.parse_str(src), raw).into_owned(),
+                    raw,
+                ),
+                ref other => panic!("expected a string, found {other:?}"),
+            },
+            other => panic!("expected a declaration, found {other:?}"),
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            (r"C:\assets\mesh.obj".to_string(), true),
+            ("a\"b".to_string(), true),
+            ("a\tb".to_string(), false),
+        ]
+    );
+}
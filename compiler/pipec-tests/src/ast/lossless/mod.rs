@@ -0,0 +1,131 @@
+use pipec_arena::{Arena, Size};
+use pipec_ast::cst::{CSTNode, CSTTree};
+use pipec_ast::tokenizer::trivia::{LosslessToken, TriviaKind};
+use pipec_ast::tokenizer::{Token, Tokenizer};
+use pipec_errors::DiagnosticEmitter;
+use pipec_file_loader::{FileId, FileLoader};
+use std::path::{Path, PathBuf};
+
+fn file_id() -> FileId {
+    let mut arena = Arena::new(Size::Kibs(1));
+    FileLoader::default().add_source(PathBuf::from("test.pipec"), "", &mut arena)
+}
+
+fn lossless_tokens(src: &str) -> Vec<LosslessToken> {
+    Tokenizer::new(src, file_id(), DiagnosticEmitter::default())
+        .lossless()
+        .collect()
+}
+
+fn collect_sources(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_sources(&path, out);
+        } else if path.extension().is_some_and(|v| v == "pipec") {
+            out.push(path);
+        }
+    }
+}
+
+#[test]
+fn test_round_trip() {
+    let mut files = Vec::new();
+    collect_sources(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut files,
+    );
+    assert!(!files.is_empty());
+    for file in files {
+        let src = std::fs::read_to_string(&file).unwrap();
+        let tree = CSTTree::new(lossless_tokens(&src), file_id());
+        assert_eq!(tree.text(&src), src, "{}", file.display());
+    }
+}
+
+#[test]
+fn test_trivia() {
+    let src = include_str!("trivia.pipec");
+    let tokens = lossless_tokens(src);
+    let kinds = |trivia: &[pipec_ast::tokenizer::trivia::Trivia]| -> Vec<TriviaKind> {
+        trivia.iter().map(|v| v.kind).collect()
+    };
+
+    let semicolon = &tokens[4];
+    assert_eq!(semicolon.token, Token::Semicolon);
+    assert_eq!(
+        kinds(&semicolon.trailing),
+        [TriviaKind::Whitespace, TriviaKind::LineComment]
+    );
+
+    let function = &tokens[5];
+    assert_eq!(function.token, Token::FunctionKeyword);
+    assert_eq!(
+        kinds(&function.leading),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::BlockComment,
+            TriviaKind::Whitespace
+        ]
+    );
+
+    let b = tokens
+        .iter()
+        .position(|v| matches!(v.token, Token::Ident(name) if name.parse_str(src) == "b"))
+        .unwrap();
+    assert_eq!(
+        kinds(&tokens[b].leading),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Whitespace
+        ]
+    );
+    assert_eq!(kinds(&tokens[b].trailing), [TriviaKind::Whitespace]);
+    // `$` is no token, it is kept in front of the token that follows it
+    assert_eq!(kinds(&tokens[b + 1].trailing), [TriviaKind::Whitespace]);
+    assert_eq!(
+        kinds(&tokens[b + 2].leading),
+        [TriviaKind::Invalid, TriviaKind::Whitespace]
+    );
+
+    let eof = tokens.last().unwrap();
+    assert_eq!(eof.token, Token::EOF);
+    assert_eq!(
+        kinds(&eof.leading),
+        [
+            TriviaKind::Whitespace,
+            TriviaKind::LineComment,
+            TriviaKind::Whitespace
+        ]
+    );
+}
+
+#[test]
+fn test_delimiters() {
+    let src = include_str!("unbalanced.pipec");
+    let tree = CSTTree::new(lossless_tokens(src), file_id());
+    assert_eq!(tree.text(src), src);
+    let Some(CSTNode::Delimited {
+        children, close, ..
+    }) = tree.nodes.last()
+    else {
+        panic!("expected the function body, found {:?}", tree.nodes.last());
+    };
+    assert!(close.is_none());
+    // `]` does not close `(`, so the call stays open up to the end of the source
+    let Some(CSTNode::Delimited {
+        children, close, ..
+    }) = children.get(1)
+    else {
+        panic!("expected the call arguments, found {:?}", children.get(1));
+    };
+    assert!(close.is_none());
+    assert!(matches!(
+        children.last(),
+        Some(CSTNode::Token(LosslessToken {
+            token: Token::Semicolon,
+            ..
+        }))
+    ));
+}
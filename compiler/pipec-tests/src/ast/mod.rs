@@ -1,6 +1,22 @@
+mod builtins;
+mod components;
+mod controlflow;
+mod diagnostics;
+mod docs;
+mod entry;
+mod expressions;
 mod functiondeclaration;
 mod generics;
+mod items;
+mod literals;
+mod lossless;
+mod numbers;
+mod recovery;
+mod spans;
+mod statics;
 mod traits;
+mod unicode;
 mod usingstatements;
 mod variablemutability;
 mod viewportdeclaration;
+mod visibility;
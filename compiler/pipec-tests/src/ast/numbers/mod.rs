@@ -0,0 +1,37 @@
+use pipec_errors::Error;
+use pipec_errors::ErrorKind;
+
+#[test]
+fn test_valid_numbers() {
+    crate::test_file_generation!("valid.pipec");
+}
+
+#[test]
+fn test_invalid_numbers() {
+    crate::test_file_generation!("invalid.pipec", diagnostics diagnostics);
+    let messages: Vec<(Option<u16>, String)> = diagnostics
+        .diagnostics()
+        .iter()
+        .map(|v| (v.code, v.message.clone()))
+        .collect();
+    let invalid = Some(ErrorKind::InvalidNumberLiteral.code());
+    let range = Some(ErrorKind::NumberOutOfRange.code());
+    assert_eq!(
+        messages,
+        [
+            (invalid, "no digits in hexadecimal literal".to_string()),
+            (invalid, "invalid digit `2` in binary literal".to_string()),
+            (
+                invalid,
+                "invalid suffix `u7` for number literal".to_string()
+            ),
+            (
+                invalid,
+                "float literal can't have the integer suffix `unsigned8`".to_string()
+            ),
+            (range, "literal out of range for `unsigned8`".to_string()),
+            (range, "literal out of range for `unsigned64`".to_string()),
+            (range, "literal out of range for `float32`".to_string()),
+        ]
+    );
+}
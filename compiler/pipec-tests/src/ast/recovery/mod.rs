@@ -0,0 +1,20 @@
+#[test]
+fn test_recovery() {
+    crate::test_file_generation!("test.pipec",diagnostics diagnostics);
+    let messages: Vec<String> = diagnostics
+        .diagnostics()
+        .into_iter()
+        .map(|v| v.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "expected `:`, found identifier",
+            "expected an expression, found `;`",
+            "expected `:` or `=`, found number",
+            "expected an item, found `}`",
+            "expected a type, found `;`",
+            "expected `;`, found end of file",
+        ]
+    );
+}
@@ -0,0 +1,115 @@
+use pipec_arena::{Arena, Size};
+use pipec_ast::RecursiveGuard;
+use pipec_ast::ast::{ASTGenerator, ASTNode, Expression, FunctionBlockStatements, Spanned};
+use pipec_ast::tokenizer::Tokenizer;
+use pipec_errors::DiagnosticEmitter;
+use pipec_file_loader::FileLoader;
+use std::path::PathBuf;
+
+fn text<T>(node: &Spanned<T>, src: &str) -> String {
+    node.span.parse_str(src).to_string()
+}
+
+#[test]
+fn test_node_spans() {
+    crate::test_file_generation!("spans.pipec", ast ast_tree);
+    let src = include_str!("spans.pipec");
+
+    let [using, public] = ast_tree.stream.as_slice() else {
+        panic!("unexpected items {:?}", ast_tree.stream);
+    };
+    assert_eq!(text(using, src), "using math\\add;");
+    assert!(
+        public
+            .span
+            .parse_str(src)
+            .starts_with("public function area")
+    );
+    assert!(public.span.parse_str(src).ends_with("return scaled;\n}"));
+    assert_eq!(public.file, ast_tree.id);
+
+    let ASTNode::Public(function) = &public.node else {
+        panic!("expected a public item, found {public:?}");
+    };
+    assert!(text(function, src).starts_with("function area"));
+    let ASTNode::FunctionDeclaration { block, .. } = &function.node else {
+        panic!("expected a function, found {function:?}");
+    };
+    let statements: Vec<String> = block.0.iter().map(|v| text(v, src)).collect();
+    assert_eq!(
+        statements,
+        [
+            "immutable scaled = add(w, 2) * h;",
+            "verts[i].normalize();",
+            "return scaled;",
+        ]
+    );
+
+    let FunctionBlockStatements::ImmutableVariableDeclaration {
+        declarationexpression: Some(expression),
+        ..
+    } = &block.0[0].node
+    else {
+        panic!("unexpected statement {:?}", block.0[0]);
+    };
+    assert_eq!(text(expression, src), "add(w, 2) * h");
+    let Expression::BinaryOpExpression { lhs, rhs, .. } = &expression.node else {
+        panic!("expected a binary expression, found {expression:?}");
+    };
+    assert_eq!(text(lhs, src), "add(w, 2)");
+    assert_eq!(text(rhs, src), "h");
+    let Expression::CallExpression {
+        function,
+        arguments,
+    } = &lhs.node
+    else {
+        panic!("expected a call, found {lhs:?}");
+    };
+    assert_eq!(text(function, src), "add");
+    let arguments: Vec<String> = arguments.iter().map(|v| text(v, src)).collect();
+    assert_eq!(arguments, ["w", "2"]);
+
+    let FunctionBlockStatements::ExpressionStatement { expression, .. } = &block.0[1].node else {
+        panic!("unexpected statement {:?}", block.0[1]);
+    };
+    assert_eq!(text(expression, src), "verts[i].normalize()");
+    let Expression::MethodCallExpression { receiver, .. } = &expression.node else {
+        panic!("expected a method call, found {expression:?}");
+    };
+    assert_eq!(text(receiver, src), "verts[i]");
+}
+
+#[test]
+fn test_error_spans() {
+    let src = "function f() => nothing {\n\timmutable = 1;\n\tg();\n}\n";
+    let mut arena = Arena::new(Size::Megs(1));
+    let mut loader = FileLoader::default();
+    let path = PathBuf::from("error.pipec");
+    let file_id = loader.add_source(path.clone(), src, &mut arena);
+    let diagnostics = DiagnosticEmitter::default();
+    let mut tokentree = Tokenizer::new(src, file_id, diagnostics.clone()).tree();
+    let mut guard = RecursiveGuard::default();
+    let ast_tree = ASTGenerator::new(
+        file_id,
+        &mut tokentree,
+        path,
+        &mut arena,
+        &mut guard,
+        &mut loader,
+        diagnostics.clone(),
+    )
+    .tree();
+    assert_eq!(diagnostics.diagnostics().len(), 1);
+
+    let ASTNode::FunctionDeclaration { block, .. } = &ast_tree.stream[0].node else {
+        panic!("expected a function, found {:?}", ast_tree.stream[0]);
+    };
+    // the placeholder covers every token skipped while recovering
+    let FunctionBlockStatements::ExpressionStatement { expression, .. } = &block.0[0].node else {
+        panic!("unexpected statement {:?}", block.0[0]);
+    };
+    assert!(matches!(expression.node, Expression::Error));
+    assert_eq!(text(&block.0[0], src), "immutable = 1;");
+    assert_eq!(text(expression, src), "immutable = 1;");
+    assert_eq!(text(&block.0[1], src), "g();");
+}
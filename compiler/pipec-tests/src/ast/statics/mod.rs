@@ -0,0 +1,98 @@
+use pipec_errors::{Error, ErrorKind};
+use pipec_gst::Symbol;
+
+#[test]
+fn test_statics() {
+    crate::test_file_generation!("globals.pipec", scope scope);
+    let src = include_str!("globals.pipec");
+
+    let Some(Symbol::Static {
+        constant,
+        public,
+        docs,
+        ..
+    }) = scope.symbols.get("PALETTE")
+    else {
+        panic!(
+            "expected a static, found {:?}",
+            scope.symbols.get("PALETTE")
+        );
+    };
+    assert!(!constant);
+    assert!(public);
+    assert_eq!(docs.text(src), "The colors every view is drawn with.");
+
+    for (name, is_constant) in [
+        ("WIDTH", true),
+        ("HEIGHT", true),
+        ("ORIGIN", false),
+        ("TITLE", false),
+        ("DEBUG", false),
+    ] {
+        let Some(Symbol::Static {
+            constant, public, ..
+        }) = scope.symbols.get(name)
+        else {
+            panic!("expected a static, found {:?}", scope.symbols.get(name));
+        };
+        assert_eq!(*constant, is_constant, "{name}");
+        assert!(!public, "{name}");
+    }
+}
+
+#[test]
+fn test_non_constant_initializers() {
+    crate::test_file_generation!("nonconstant.pipec", scope scope, diagnostics diagnostics);
+    let src = include_str!("nonconstant.pipec");
+    let diagnostics = diagnostics.diagnostics();
+    let reported: Vec<(&str, &str)> = diagnostics
+        .iter()
+        .map(|v| {
+            assert_eq!(v.code, Some(ErrorKind::NonConstantInitializer.code()));
+            let label = v.primary.as_ref().unwrap();
+            (v.message.as_str(), label.span.parse_str(src))
+        })
+        .collect();
+    assert_eq!(
+        reported,
+        [
+            (
+                "`WIDTH` must be initialized with a constant expression",
+                "width()"
+            ),
+            (
+                "`SIZE` must be initialized with a constant expression",
+                "if true { 1 } else { 2 }"
+            ),
+            (
+                "`CELLS` must be initialized with a constant expression",
+                "WIDTH.cells"
+            ),
+            (
+                "`CALLBACK` must be initialized with a constant expression",
+                "width"
+            ),
+            (
+                "`BORDER` must be initialized with a constant expression",
+                "width"
+            ),
+            (
+                "`MISSING` must be initialized with a constant expression",
+                "HEIGHT"
+            ),
+        ]
+    );
+    let labels: Vec<&str> = diagnostics[3..]
+        .iter()
+        .filter_map(|v| v.primary.as_ref()?.message.as_deref())
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            "this is not a static or constant",
+            "this is not a static or constant",
+            "no static or constant with this name",
+        ]
+    );
+    assert!(scope.symbols.contains_key("VALID"));
+}
@@ -0,0 +1,47 @@
+use pipec_ast::tokenizer::{Token, Tokenizer};
+use pipec_errors::DiagnosticEmitter;
+use pipec_file_loader::FileLoader;
+use std::path::PathBuf;
+
+#[test]
+fn test_identifiers() {
+    let src = include_str!("names.pipec");
+    let mut arena = pipec_arena::Arena::new(pipec_arena::Size::Kibs(4));
+    let file = FileLoader::default().add_source(PathBuf::from("names.pipec"), src, &mut arena);
+    let diagnostics = DiagnosticEmitter::default();
+    let names: Vec<&str> = Tokenizer::new(src, file, diagnostics.clone())
+        .filter_map(|(token, _)| match token {
+            Token::Ident(name) => Some(name.parse_str(src)),
+            _ => None,
+        })
+        .collect();
+    assert!(diagnostics.is_empty(), "{:#?}", diagnostics.diagnostics());
+    assert_eq!(
+        names,
+        [
+            "maße",
+            "fla\u{308}che",
+            "nothing",
+            "maße",
+            "fläche",
+            "_unused",
+            "nothing",
+            "_",
+            "größe",
+            "fläche",
+            "π",
+            "名前",
+            "größe",
+            "π",
+        ]
+    );
+}
+
+#[test]
+fn test_normalized_symbols() {
+    crate::test_file_generation!("names.pipec", scope scope);
+    // declared decomposed, found by its composed spelling
+    assert!(scope.submodules["maße"].symbols.contains_key("fläche"));
+    assert!(scope.symbols.contains_key("fläche"));
+    assert!(scope.symbols.contains_key("_unused"));
+}
@@ -0,0 +1,47 @@
+use pipec_errors::{Error, ErrorKind};
+
+#[test]
+fn test_public_items() {
+    crate::test_file_generation!("public.pipec", scope scope);
+    let shapes = &scope.submodules["shapes"];
+    assert!(!shapes.public);
+    assert!(shapes.symbols["area"].is_public());
+    assert!(!shapes.symbols["helper"].is_public());
+    assert!(shapes.submodules["circles"].public);
+    assert!(!shapes.submodules["internal"].public);
+    assert!(scope.symbols["SCALE"].is_public());
+    assert!(!scope.symbols["main"].is_public());
+    assert!(!scope.symbols["area"].is_public());
+}
+
+#[test]
+fn test_private_items() {
+    crate::test_file_generation!("private.pipec", scope scope, diagnostics diagnostics);
+    let src = include_str!("private.pipec");
+    let diagnostics = diagnostics.diagnostics();
+    // symbols are checked in no particular order
+    let reported: Vec<(&str, &str)> = diagnostics
+        .iter()
+        .map(|v| {
+            assert_eq!(v.code, Some(ErrorKind::PrivateItem.code()));
+            let label = v.primary.as_ref().unwrap();
+            (v.message.as_str(), label.span.parse_str(src))
+        })
+        .collect();
+    assert_eq!(reported.len(), 4, "{reported:?}");
+    for expected in [
+        ("`hidden` is private", "hidden"),
+        ("module `internal` is private", "internal"),
+        ("`LIMIT` is private", "LIMIT"),
+    ] {
+        assert!(reported.contains(&expected), "{reported:?}");
+    }
+    assert_eq!(
+        reported
+            .iter()
+            .filter(|v| **v == ("`hidden` is private", "hidden"))
+            .count(),
+        2
+    );
+    assert!(!scope.symbols.contains_key("hidden"));
+}
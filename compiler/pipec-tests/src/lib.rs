@@ -1,74 +1,119 @@
 #[cfg(test)]
 mod ast;
 
-#[macro_export]
-macro_rules! test_file_generation {
-    ($filename:  literal) => {
-        use pipec_arena::{Arena, Size};
-        use pipec_ast::{RecursiveGuard, ast::ASTGenerator, tokenizer::Tokenizer};
-        use pipec_file_loader::FileLoader;
+use pipec_arena::{Arena, Size};
+use pipec_ast::{
+    RecursiveGuard,
+    ast::{ASTGenerator, asttree::ASTTree},
+    tokenizer::Tokenizer,
+};
+use pipec_errors::DiagnosticEmitter;
+use pipec_file_loader::FileLoader;
+use pipec_gst::{CrateType, GlobalSymbolTree, ModuleScope};
+
+/// A parsed test file, the arena and loader are kept alive for the scope built from it.
+pub struct TestFile {
+    pub arena: Arena,
+    pub loader: FileLoader,
+    pub ast: ASTTree,
+    pub diagnostics: DiagnosticEmitter,
+}
 
+impl TestFile {
+    /// Parses `filename`, which sits next to the test source `source`, eg. `file!()`.
+    pub fn parse(source: &str, filename: &str, contents: &str) -> Self {
         let file_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
             .parent()
             .unwrap()
-            .join(file!())
+            .join(source)
             .parent()
             .unwrap()
-            .join($filename);
+            .join(filename);
 
         let mut arena = Arena::new(Size::Megs(10));
         let mut loader = FileLoader::default();
         let file_id = loader.open(&file_dir, &mut arena).unwrap();
 
-        let file_contents = include_str!($filename);
-        let mut tokentree = Tokenizer::new(&file_contents).tree();
+        let diagnostics = DiagnosticEmitter::default();
+        let mut tokentree = Tokenizer::new(contents, file_id, diagnostics.clone()).tree();
         let mut guard = RecursiveGuard::default();
 
-        #[allow(unused_variables)]
-        let ast_tree = ASTGenerator::new(
+        let ast = ASTGenerator::new(
             file_id,
             &mut tokentree,
             file_dir,
             &mut arena,
             &mut guard,
             &mut loader,
+            diagnostics.clone(),
         )
         .tree();
-    };
+        Self {
+            arena,
+            loader,
+            ast,
+            diagnostics,
+        }
+    }
 
-    ($filename : literal,scope $scope:ident) => {
-        use pipec_arena::{Arena, Size};
-        use pipec_ast::{RecursiveGuard, ast::ASTGenerator, tokenizer::Tokenizer};
-        use pipec_file_loader::FileLoader;
-        use pipec_gst::GlobalSymbolTree;
+    /// Builds the global symbol tree of the file as a crate of the given type.
+    pub fn scope<'a>(&mut self, crate_type: CrateType) -> ModuleScope<'a> {
+        GlobalSymbolTree::new(
+            &mut self.arena,
+            &mut self.loader,
+            self.ast.clone(),
+            self.diagnostics.clone(),
+        )
+        .with_crate_type(crate_type)
+        .generate()
+    }
+}
 
-        let file_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-            .parent()
-            .unwrap()
-            .join(file!())
-            .parent()
-            .unwrap()
-            .join($filename);
+#[macro_export]
+macro_rules! test_file_generation {
+    ($filename:literal) => {
+        let file = $crate::TestFile::parse(file!(), $filename, include_str!($filename));
+        assert!(
+            file.diagnostics.is_empty(),
+            "{:#?}",
+            file.diagnostics.diagnostics()
+        );
+    };
 
-        let mut arena = Arena::new(Size::Megs(10));
-        let mut loader = FileLoader::default();
-        let file_id = loader.open(&file_dir, &mut arena).unwrap();
+    ($filename:literal, ast $ast:ident) => {
+        let file = $crate::TestFile::parse(file!(), $filename, include_str!($filename));
+        assert!(
+            file.diagnostics.is_empty(),
+            "{:#?}",
+            file.diagnostics.diagnostics()
+        );
+        let $ast = file.ast;
+    };
 
-        let file_contents = include_str!($filename);
-        let mut tokentree = Tokenizer::new(&file_contents).tree();
-        let mut guard = RecursiveGuard::default();
+    ($filename:literal, diagnostics $diagnostics:ident) => {
+        let $diagnostics =
+            $crate::TestFile::parse(file!(), $filename, include_str!($filename)).diagnostics;
+    };
 
-        let ast_tree = ASTGenerator::new(
-            file_id,
-            &mut tokentree,
-            file_dir,
-            &mut arena,
-            &mut guard,
-            &mut loader,
-        )
-        .tree();
+    ($filename:literal, scope $scope:ident) => {
+        let mut file = $crate::TestFile::parse(file!(), $filename, include_str!($filename));
+        let $scope = file.scope(pipec_gst::CrateType::Library);
+        assert!(
+            file.diagnostics.is_empty(),
+            "{:#?}",
+            file.diagnostics.diagnostics()
+        );
+    };
+
+    ($filename:literal, scope $scope:ident, diagnostics $diagnostics:ident) => {
+        let mut file = $crate::TestFile::parse(file!(), $filename, include_str!($filename));
+        let $scope = file.scope(pipec_gst::CrateType::Library);
+        let $diagnostics = file.diagnostics.clone();
+    };
 
-        let mut gst = GlobalSymbolTree::new(&mut arena, &mut loader, ast_tree);
-        let $scope = gst.generate();
+    ($filename:literal, binary $scope:ident, diagnostics $diagnostics:ident) => {
+        let mut file = $crate::TestFile::parse(file!(), $filename, include_str!($filename));
+        let $scope = file.scope(pipec_gst::CrateType::Binary);
+        let $diagnostics = file.diagnostics.clone();
     };
 }